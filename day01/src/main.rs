@@ -1,45 +1,308 @@
+use std::error::Error;
+use std::fmt;
+use std::num::IntErrorKind;
+use std::thread;
 use std::{env, fs, io, path::Path};
 
+mod parallel;
+mod planner;
+mod stats;
+
+use stats::Stats;
+
 fn main() -> io::Result<()> {
     let mut dir = env::current_exe()?;
     dir.pop();
     let dir = dir.join(Path::new("../../day01/input.txt"));
 
-    let input = fs::read_to_string(dir)?;
-    println!("part1: {}", part1(&input));
-    println!("part2: {}", part2(&input));
+    let (flags, args): (Vec<String>, Vec<String>) = env::args().skip(1).partition(|a| a.starts_with("--"));
+    let mode = if flags.iter().any(|f| f == "--lenient") { Mode::Lenient } else { Mode::Strict };
+    let path = flags
+        .iter()
+        .find_map(|f| f.strip_prefix("--input="))
+        .map_or(dir, |p| p.into());
+
+    let input = fs::read_to_string(path)?;
+
+    // Huge inputs are summed without ever building the full inventory.
+    if args.first().map(String::as_str) == Some("parallel") {
+        let threads = args
+            .get(1)
+            .and_then(|t| t.parse().ok())
+            .or_else(|| thread::available_parallelism().ok().map(|n| n.get()))
+            .unwrap_or(1);
+        let summary = parallel::summarize(&input, mode, threads, 3)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        for warning in &summary.warnings {
+            eprintln!("warning: {}", warning);
+        }
+        println!("part1: {}", summary.max());
+        println!("part2: {}", summary.top_sum(3));
+        return Ok(());
+    }
+
+    let inventory = Inventory::parse(&input, mode)
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+    for warning in &inventory.warnings {
+        eprintln!("warning: {}", warning);
+    }
+
+    match args.first().map(String::as_str) {
+        Some("elf") => {
+            let id = args
+                .get(1)
+                .and_then(|id| id.parse::<usize>().ok())
+                .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "usage: day01 elf <id>"))?;
+            match inventory.elf(id) {
+                Some(elf) => println!("{}", elf),
+                None => println!("no elf #{}", id),
+            }
+        }
+        Some("plan") => {
+            let k = args
+                .get(1)
+                .and_then(|k| k.parse::<usize>().ok())
+                .filter(|&k| k > 0)
+                .ok_or_else(|| {
+                    io::Error::new(io::ErrorKind::InvalidInput, "usage: day01 plan <groups> [greedy|kk|exact]")
+                })?;
+            let plan = match args.get(2).map(String::as_str) {
+                Some("greedy") => planner::greedy(&inventory, k),
+                Some("kk") => planner::karmarkar_karp(&inventory, k),
+                Some("exact") => planner::exact(&inventory, k).ok_or_else(|| {
+                    io::Error::new(
+                        io::ErrorKind::InvalidInput,
+                        format!("exact planning is limited to {} elves", planner::EXACT_LIMIT),
+                    )
+                })?,
+                _ => planner::plan(&inventory, k),
+            };
+            print!("{}", plan);
+        }
+        Some("stats") => {
+            let bins = args.get(1).and_then(|b| b.parse().ok()).unwrap_or(10);
+            match Stats::new(&inventory, bins) {
+                Some(stats) => print!("{}", stats),
+                None => println!("no elves"),
+            }
+        }
+        _ => {
+            println!("part1: {}", part1(&inventory));
+            for elf in inventory.top(1) {
+                println!("  {}", elf);
+            }
+            println!("part2: {}", part2(&inventory));
+            for elf in inventory.top(3) {
+                println!("  {}", elf);
+            }
+        }
+    }
     Ok(())
 }
 
-fn part1(input: &str) -> String {
-    let max = input
-        .split("\n\n")
-        .map(|food| {
-            food
-                .split('\n')
-                .map(|c| c.parse::<u32>().unwrap_or(0))
-                .sum::<u32>()
-        })
-        .max().unwrap();
+/// A single elf's food inventory, identified by its 1-based position in the input.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Elf {
+    id: usize,
+    items: Vec<u64>,
+}
+
+impl Elf {
+    fn total(&self) -> u64 {
+        self.items.iter().sum()
+    }
+}
+
+impl fmt::Display for Elf {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "elf #{} carries {} calories in {} item{}",
+            self.id,
+            thousands(self.total()),
+            self.items.len(),
+            if self.items.len() == 1 { "" } else { "s" }
+        )
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Mode {
+    /// Reject the whole input on the first malformed line.
+    Strict,
+    /// Skip malformed lines, recording each one as a warning.
+    Lenient,
+}
+
+/// A problem found while parsing, tagged with the 1-based input line it was found on.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum ParseError {
+    InvalidCalories { line: usize, text: String },
+    EmptyInventory { line: usize },
+    Overflow { line: usize },
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::InvalidCalories { line, text } => {
+                write!(f, "line {}: invalid calorie count {:?}", line, text)
+            }
+            Self::EmptyInventory { line } => {
+                write!(f, "line {}: blank line does not close an inventory", line)
+            }
+            Self::Overflow { line } => write!(f, "line {}: calorie total overflows u64", line),
+        }
+    }
+}
+
+impl ParseError {
+    /// Moves the error `lines` further down the input, for errors found in a
+    /// slice that does not start at the first line.
+    fn offset(self, lines: usize) -> Self {
+        match self {
+            Self::InvalidCalories { line, text } => Self::InvalidCalories { line: line + lines, text },
+            Self::EmptyInventory { line } => Self::EmptyInventory { line: line + lines },
+            Self::Overflow { line } => Self::Overflow { line: line + lines },
+        }
+    }
+}
+
+impl Error for ParseError {}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Inventory {
+    elves: Vec<Elf>,
+    warnings: Vec<ParseError>,
+}
+
+impl Inventory {
+    /// Parses blank-line separated groups of calorie counts. Besides each elf's
+    /// own total, the grand total is checked for overflow so any sum over a
+    /// subset of elves is safe to compute afterwards.
+    fn parse(input: &str, mode: Mode) -> Result<Self, ParseError> {
+        let mut elves: Vec<Elf> = Vec::new();
+        let warnings = scan(input, mode, |id, items| {
+            elves.push(Elf { id, items: items.to_vec() })
+        })?;
+
+        Ok(Inventory { elves, warnings })
+    }
+
+    /// The elf with the inventory at position `id`. Lenient parsing can skip
+    /// inventories, so ids may have gaps, but the elves stay in id order.
+    fn elf(&self, id: usize) -> Option<&Elf> {
+        self.elves.binary_search_by_key(&id, |e| e.id).ok().map(|i| &self.elves[i])
+    }
+
+    /// The `n` elves carrying the most calories, heaviest first, extended with
+    /// any elves tied with the last one so ties are never dropped arbitrarily.
+    fn top(&self, n: usize) -> Vec<&Elf> {
+        let mut ranked = self.elves.iter().collect::<Vec<_>>();
+        ranked.sort_by(|a, b| b.total().cmp(&a.total()).then(a.id.cmp(&b.id)));
+
+        if let Some(cutoff) = ranked.get(n.saturating_sub(1)).map(|e| e.total()) {
+            let keep = ranked
+                .iter()
+                .position(|e| e.total() < cutoff)
+                .unwrap_or(ranked.len());
+            ranked.truncate(keep.max(n));
+        }
+        ranked
+    }
+}
+
+/// Walks the blank-line separated inventories of `input`, handing each elf's
+/// position from 1 and items to `visit` in order, and returns the warnings
+/// collected in lenient mode. An inventory whose lines were all skipped is
+/// not visited but still counts towards the positions of the ones after it.
+fn scan(input: &str, mode: Mode, mut visit: impl FnMut(usize, &[u64])) -> Result<Vec<ParseError>, ParseError> {
+    let mut warnings = Vec::new();
+    let mut items = Vec::new();
+    let mut block = 0;
+    let mut open = false;
+    let mut grand_total = 0u64;
+
+    let mut reject = |error: ParseError| match mode {
+        Mode::Strict => Err(error),
+        Mode::Lenient => {
+            warnings.push(error);
+            Ok(())
+        }
+    };
+
+    for (i, text) in input.lines().enumerate() {
+        let line = i + 1;
+        if text.trim().is_empty() {
+            if !open {
+                reject(ParseError::EmptyInventory { line })?;
+            } else if !items.is_empty() {
+                visit(block, &items);
+                items.clear();
+            }
+            open = false;
+            continue;
+        }
+        if !open {
+            open = true;
+            block += 1;
+        }
+
+        match text.parse::<u64>() {
+            Ok(calories) => match grand_total.checked_add(calories) {
+                Some(total) => {
+                    grand_total = total;
+                    items.push(calories);
+                }
+                None => reject(ParseError::Overflow { line })?,
+            },
+            Err(e) if *e.kind() == IntErrorKind::PosOverflow => {
+                reject(ParseError::Overflow { line })?
+            }
+            Err(_) => reject(ParseError::InvalidCalories { line, text: text.to_string() })?,
+        }
+    }
+    if !items.is_empty() {
+        visit(block, &items);
+    }
+
+    Ok(warnings)
+}
+
+fn thousands(n: u64) -> String {
+    let digits = n.to_string();
+    let mut out = String::with_capacity(digits.len() + digits.len() / 3);
+    for (i, c) in digits.chars().enumerate() {
+        if i > 0 && (digits.len() - i).is_multiple_of(3) {
+            out.push(',');
+        }
+        out.push(c);
+    }
+    out
+}
+
+fn part1(inventory: &Inventory) -> String {
+    let max = inventory
+        .elves
+        .iter()
+        .map(Elf::total)
+        .max()
+        .unwrap_or(0);
 
     max.to_string()
 }
 
-fn part2(input: &str) -> String {
-    let mut calories = input
-        .split("\n\n")
-        .map(|food| {
-            food
-                .split("\n")
-                .map(|c| c.parse::<u32>().unwrap_or(0))
-                .sum::<u32>()
-        })
+fn part2(inventory: &Inventory) -> String {
+    let mut calories = inventory
+        .elves
+        .iter()
+        .map(Elf::total)
         .collect::<Vec<_>>();
 
     calories.sort_unstable_by(|a, b| b.cmp(a));
     let max = calories.iter()
         .take(3)
-        .sum::<u32>();
+        .sum::<u64>();
 
     max.to_string()
 }
@@ -48,7 +311,7 @@ fn part2(input: &str) -> String {
 mod tests {
     use super::*;
 
-    const INPUT: &str = "1000
+    pub(crate) const INPUT: &str = "1000
 2000
 3000
 
@@ -63,13 +326,95 @@ mod tests {
 
 10000";
 
+    fn parse(input: &str) -> Inventory {
+        Inventory::parse(input, Mode::Strict).unwrap()
+    }
+
     #[test]
     fn test_part1() {
-        assert_eq!("24000", part1(INPUT));
+        assert_eq!("24000", part1(&parse(INPUT)));
     }
 
     #[test]
     fn test_part2() {
-        assert_eq!("45000", part2(INPUT));
+        assert_eq!("45000", part2(&parse(INPUT)));
+    }
+
+    #[test]
+    fn test_inventory_keeps_positions() {
+        let inventory = parse(INPUT);
+        assert_eq!(5, inventory.elves.len());
+        assert_eq!(Some(&Elf { id: 4, items: vec![7000, 8000, 9000] }), inventory.elf(4));
+        assert_eq!(None, inventory.elf(0));
+        assert_eq!(None, inventory.elf(6));
+    }
+
+    #[test]
+    fn test_top_includes_ties() {
+        let inventory = parse("300\n\n100\n200\n\n50");
+        let ids = inventory.top(1).iter().map(|e| e.id).collect::<Vec<_>>();
+        assert_eq!(vec![1, 2], ids);
+
+        let ids = parse(INPUT).top(3).iter().map(|e| e.id).collect::<Vec<_>>();
+        assert_eq!(vec![4, 3, 5], ids);
+    }
+
+    #[test]
+    fn test_display() {
+        let elf = Elf { id: 173, items: vec![71023] };
+        assert_eq!("elf #173 carries 71,023 calories in 1 item", elf.to_string());
+        assert_eq!("1,000,000", thousands(1_000_000));
+        assert_eq!("999", thousands(999));
+    }
+
+    #[test]
+    fn test_trailing_newline_adds_no_item() {
+        let inventory = parse("100\n200\n\n300\n");
+        assert_eq!(vec![100, 200], inventory.elves[0].items);
+        assert_eq!(vec![300], inventory.elves[1].items);
+    }
+
+    #[test]
+    fn test_strict_rejects_malformed_lines() {
+        let invalid = ParseError::InvalidCalories { line: 2, text: "-5".into() };
+        assert_eq!(Err(invalid), Inventory::parse("100\n-5\n", Mode::Strict));
+
+        let invalid = ParseError::InvalidCalories { line: 3, text: "1o0".into() };
+        assert_eq!(Err(invalid), Inventory::parse("1\n\n1o0", Mode::Strict));
+
+        let empty = ParseError::EmptyInventory { line: 3 };
+        assert_eq!(Err(empty), Inventory::parse("1\n\n\n2", Mode::Strict));
+
+        let overflow = ParseError::Overflow { line: 1 };
+        assert_eq!(Err(overflow), Inventory::parse("18446744073709551616", Mode::Strict));
+
+        let overflow = ParseError::Overflow { line: 3 };
+        assert_eq!(Err(overflow), Inventory::parse("18446744073709551615\n\n1", Mode::Strict));
+    }
+
+    #[test]
+    fn test_lenient_reports_skipped_lines() {
+        let inventory = Inventory::parse("100\nabc\n\n\n200", Mode::Lenient).unwrap();
+        assert_eq!(vec![100], inventory.elves[0].items);
+        assert_eq!(Elf { id: 2, items: vec![200] }, inventory.elves[1]);
+        assert_eq!(
+            vec![
+                ParseError::InvalidCalories { line: 2, text: "abc".into() },
+                ParseError::EmptyInventory { line: 4 },
+            ],
+            inventory.warnings
+        );
+
+        let inventory = Inventory::parse("100\n\nabc\n\n200", Mode::Lenient).unwrap();
+        assert_eq!(Some(&Elf { id: 3, items: vec![200] }), inventory.elf(3));
+        assert_eq!(None, inventory.elf(2));
+        assert_eq!(vec![ParseError::InvalidCalories { line: 3, text: "abc".into() }], inventory.warnings);
+    }
+
+    #[test]
+    fn test_empty_input() {
+        let inventory = parse("");
+        assert_eq!("0", part1(&inventory));
+        assert_eq!(part1(&inventory), parallel::summarize("", Mode::Strict, 2, 3).unwrap().max().to_string());
     }
 }
@@ -0,0 +1,202 @@
+use std::thread;
+
+use crate::{scan, Inventory, Mode, ParseError};
+
+/// What `part1` and `part2` need from an inventory, without keeping the items.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Summary {
+    pub elves: usize,
+    /// The heaviest `k` totals, heaviest first.
+    pub top: Vec<u64>,
+    pub warnings: Vec<ParseError>,
+}
+
+impl Summary {
+    pub fn max(&self) -> u64 {
+        self.top.first().copied().unwrap_or(0)
+    }
+
+    pub fn top_sum(&self, n: usize) -> u64 {
+        self.top.iter().take(n).sum()
+    }
+}
+
+struct Chunk {
+    lines: usize,
+    elves: usize,
+    grand_total: u64,
+    top: Vec<u64>,
+    warnings: Vec<ParseError>,
+}
+
+/// Sums `input` on `threads` threads, keeping the `k` heaviest totals. The
+/// buffer is only ever cut right after a blank line, so each chunk holds whole
+/// inventories and parses exactly as it would inside the full input; line
+/// numbers in errors and warnings are shifted back to input positions.
+pub fn summarize(input: &str, mode: Mode, threads: usize, k: usize) -> Result<Summary, ParseError> {
+    let chunks = split(input, threads.max(1));
+
+    let results = thread::scope(|s| {
+        let handles = chunks
+            .iter()
+            .map(|chunk| s.spawn(move || summarize_chunk(chunk, mode, k)))
+            .collect::<Vec<_>>();
+        handles
+            .into_iter()
+            .map(|h| h.join().expect("summing thread panicked"))
+            .collect::<Vec<_>>()
+    });
+
+    let mut summary = Summary { elves: 0, top: Vec::new(), warnings: Vec::new() };
+    let mut grand_total = 0u64;
+    let mut offset = 0;
+    for result in results {
+        let chunk = result.map_err(|e| e.offset(offset))?;
+        match grand_total.checked_add(chunk.grand_total) {
+            Some(total) => grand_total = total,
+            // Only a sequential pass knows on which line the running total
+            // overflowed, and in lenient mode which item gets skipped.
+            None => return sequential(input, mode, k),
+        }
+
+        summary.elves += chunk.elves;
+        summary.top.extend(chunk.top);
+        summary.warnings.extend(chunk.warnings.into_iter().map(|w| w.offset(offset)));
+        offset += chunk.lines;
+    }
+    summary.top.sort_unstable_by(|a, b| b.cmp(a));
+    summary.top.truncate(k);
+
+    Ok(summary)
+}
+
+fn sequential(input: &str, mode: Mode, k: usize) -> Result<Summary, ParseError> {
+    let inventory = Inventory::parse(input, mode)?;
+    let mut top = inventory.elves.iter().map(|e| e.total()).collect::<Vec<_>>();
+    top.sort_unstable_by(|a, b| b.cmp(a));
+    top.truncate(k);
+
+    Ok(Summary { elves: inventory.elves.len(), top, warnings: inventory.warnings })
+}
+
+fn summarize_chunk(input: &str, mode: Mode, k: usize) -> Result<Chunk, ParseError> {
+    let mut elves = 0;
+    let mut grand_total = 0u64;
+    let mut top: Vec<u64> = Vec::with_capacity(k + 1);
+
+    let warnings = scan(input, mode, |_, items| {
+        let total = items.iter().sum::<u64>();
+        elves += 1;
+        grand_total += total;
+
+        let at = top.partition_point(|&t| t >= total);
+        if at < k {
+            top.insert(at, total);
+            top.truncate(k);
+        }
+    })?;
+
+    Ok(Chunk {
+        lines: input.bytes().filter(|&b| b == b'\n').count(),
+        elves,
+        grand_total,
+        top,
+        warnings,
+    })
+}
+
+/// Cuts `input` into at most `n` slices of roughly equal size, each ending
+/// just after a blank line (except the last).
+fn split(input: &str, n: usize) -> Vec<&str> {
+    let bytes = input.as_bytes();
+    let mut chunks = Vec::with_capacity(n);
+    let mut start = 0;
+
+    for i in 1..n {
+        let target = (input.len() * i / n).max(start);
+        match blank_line_end(&bytes[target..]) {
+            Some(end) => {
+                let end = target + end;
+                chunks.push(&input[start..end]);
+                start = end;
+            }
+            None => break,
+        }
+    }
+    if start < input.len() || chunks.is_empty() {
+        chunks.push(&input[start..]);
+    }
+    chunks
+}
+
+/// Offset just past the first `\n\n` or `\n\r\n` in `bytes`.
+fn blank_line_end(bytes: &[u8]) -> Option<usize> {
+    bytes
+        .iter()
+        .enumerate()
+        .filter(|&(_, &b)| b == b'\n')
+        .find_map(|(i, _)| match bytes.get(i + 1..) {
+            Some([b'\n', ..]) => Some(i + 2),
+            Some([b'\r', b'\n', ..]) => Some(i + 3),
+            _ => None,
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{part1, part2, tests::INPUT};
+
+    #[test]
+    fn test_matches_sequential_parts() {
+        let inventory = Inventory::parse(INPUT, Mode::Strict).unwrap();
+        for threads in 1..=8 {
+            let summary = summarize(INPUT, Mode::Strict, threads, 3).unwrap();
+            assert_eq!(part1(&inventory), summary.max().to_string());
+            assert_eq!(part2(&inventory), summary.top_sum(3).to_string());
+            assert_eq!(5, summary.elves);
+        }
+    }
+
+    #[test]
+    fn test_split_keeps_inventories_whole() {
+        let chunks = split(INPUT, 4);
+        assert!(chunks.len() > 1);
+        assert_eq!(INPUT, chunks.concat());
+        for chunk in &chunks[..chunks.len() - 1] {
+            assert!(chunk.ends_with("\n\n"));
+        }
+
+        assert_eq!(Some(3), blank_line_end(b"1\n\n2"));
+        assert_eq!(Some(5), blank_line_end(b"1\r\n\r\n2"));
+        assert_eq!(None, blank_line_end(b"1\n2\n"));
+        assert_eq!(vec![""], split("", 4));
+    }
+
+    #[test]
+    fn test_errors_and_warnings_keep_input_lines() {
+        let input = "1\n\n2\n\n\n3\n\nx\n\n4";
+        for threads in 1..=6 {
+            assert_eq!(
+                Err(ParseError::EmptyInventory { line: 5 }),
+                summarize(input, Mode::Strict, threads, 3)
+            );
+            let summary = summarize(input, Mode::Lenient, threads, 3).unwrap();
+            assert_eq!(Inventory::parse(input, Mode::Lenient).unwrap().warnings, summary.warnings);
+            assert_eq!(vec![4, 3, 2], summary.top);
+        }
+    }
+
+    #[test]
+    fn test_grand_total_overflow_falls_back_to_sequential() {
+        let input = "18446744073709551615\n\n1\n\n2";
+        for threads in 1..=3 {
+            assert_eq!(
+                Err(ParseError::Overflow { line: 3 }),
+                summarize(input, Mode::Strict, threads, 3)
+            );
+            let summary = summarize(input, Mode::Lenient, threads, 3).unwrap();
+            assert_eq!(vec![u64::MAX], summary.top);
+        }
+    }
+}
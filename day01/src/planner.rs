@@ -0,0 +1,335 @@
+use std::fmt;
+
+use crate::{thousands, Inventory};
+
+/// Inventories with at most this many elves are small enough to solve exactly.
+pub const EXACT_LIMIT: usize = 20;
+
+/// Partial assignments the exact search may visit before giving up. The
+/// search grows with the number of groups as well as elves, so even small
+/// inventories can take minutes with many groups.
+pub const NODE_BUDGET: u64 = 1_000_000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Method {
+    Greedy,
+    KarmarkarKarp,
+    Exact,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Group {
+    /// Ids of the elves assigned to this group.
+    pub members: Vec<usize>,
+    pub total: u64,
+}
+
+impl Group {
+    fn add(&mut self, id: usize, total: u64) {
+        self.members.push(id);
+        self.total += total;
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Plan {
+    pub method: Method,
+    pub groups: Vec<Group>,
+}
+
+impl Plan {
+    /// Difference between the heaviest and the lightest group.
+    pub fn spread(&self) -> u64 {
+        spread(&self.groups)
+    }
+
+    fn new(method: Method, mut groups: Vec<Group>) -> Self {
+        for group in groups.iter_mut() {
+            group.members.sort_unstable();
+        }
+        groups.sort_by(|a, b| b.total.cmp(&a.total).then(a.members.cmp(&b.members)));
+        Plan { method, groups }
+    }
+}
+
+fn spread(groups: &[Group]) -> u64 {
+    let max = groups.iter().map(|g| g.total).max().unwrap_or(0);
+    let min = groups.iter().map(|g| g.total).min().unwrap_or(0);
+    max - min
+}
+
+/// Elf ids paired with their totals, heaviest first.
+fn weights(inventory: &Inventory) -> Vec<(usize, u64)> {
+    let mut weights = inventory
+        .elves
+        .iter()
+        .map(|e| (e.id, e.total()))
+        .collect::<Vec<_>>();
+    weights.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+    weights
+}
+
+/// Picks the exact solver when it finishes within its limits, otherwise the
+/// better of the two heuristics.
+pub fn plan(inventory: &Inventory, k: usize) -> Plan {
+    exact(inventory, k).unwrap_or_else(|| {
+        let greedy = greedy(inventory, k);
+        let kk = karmarkar_karp(inventory, k);
+        if kk.spread() < greedy.spread() { kk } else { greedy }
+    })
+}
+
+/// Longest-processing-time heuristic: hand out elves heaviest first, each to
+/// the currently lightest group.
+pub fn greedy(inventory: &Inventory, k: usize) -> Plan {
+    let mut groups = vec![Group::default(); k.max(1)];
+    for (id, total) in weights(inventory) {
+        let lightest = groups
+            .iter_mut()
+            .min_by_key(|g| g.total)
+            .unwrap();
+        lightest.add(id, total);
+    }
+    Plan::new(Method::Greedy, groups)
+}
+
+/// Largest differencing method generalised to `k` groups: every elf starts
+/// as a partial partition with one non-empty group, and the two partials with
+/// the largest spread are repeatedly merged by pairing the heaviest groups of
+/// one with the lightest groups of the other.
+pub fn karmarkar_karp(inventory: &Inventory, k: usize) -> Plan {
+    let k = k.max(1);
+    let mut partials = weights(inventory)
+        .into_iter()
+        .map(|(id, total)| {
+            let mut groups = vec![Group::default(); k];
+            groups[0].add(id, total);
+            groups
+        })
+        .collect::<Vec<_>>();
+
+    while partials.len() > 1 {
+        partials.sort_by_key(|p| spread(p));
+        let mut a = partials.pop().unwrap();
+        let mut b = partials.pop().unwrap();
+        a.sort_by_key(|g| std::cmp::Reverse(g.total));
+        b.sort_by_key(|g| g.total);
+
+        let merged = a
+            .into_iter()
+            .zip(b)
+            .map(|(mut x, y)| {
+                x.members.extend(y.members);
+                x.total += y.total;
+                x
+            })
+            .collect();
+        partials.push(merged);
+    }
+
+    let groups = partials.pop().unwrap_or_else(|| vec![Group::default(); k]);
+    Plan::new(Method::KarmarkarKarp, groups)
+}
+
+/// Branch and bound search for the assignment with the smallest spread. The
+/// search is exponential, so inventories larger than [`EXACT_LIMIT`] are
+/// refused and searches visiting more than [`NODE_BUDGET`] partial
+/// assignments are abandoned.
+pub fn exact(inventory: &Inventory, k: usize) -> Option<Plan> {
+    if inventory.elves.len() > EXACT_LIMIT {
+        return None;
+    }
+
+    struct Search {
+        weights: Vec<(usize, u64)>,
+        /// The lightest group can never end above the average, so no
+        /// completion can have a spread below `heaviest - floor`.
+        floor: u64,
+        perfect: u64,
+        totals: Vec<u64>,
+        assignment: Vec<usize>,
+        best_spread: u64,
+        best: Vec<usize>,
+        nodes: u64,
+    }
+
+    impl Search {
+        fn run(&mut self, i: usize) {
+            if self.best_spread == self.perfect || self.nodes > NODE_BUDGET {
+                return;
+            }
+            self.nodes += 1;
+            let heaviest = *self.totals.iter().max().unwrap();
+            if heaviest.saturating_sub(self.floor) >= self.best_spread {
+                return;
+            }
+            if i == self.weights.len() {
+                let lightest = *self.totals.iter().min().unwrap();
+                if heaviest - lightest < self.best_spread {
+                    self.best_spread = heaviest - lightest;
+                    self.best = self.assignment.clone();
+                }
+                return;
+            }
+
+            for g in 0..self.totals.len() {
+                // Groups with equal totals are interchangeable from here on.
+                if self.totals[..g].contains(&self.totals[g]) {
+                    continue;
+                }
+                self.totals[g] += self.weights[i].1;
+                self.assignment[i] = g;
+                self.run(i + 1);
+                self.totals[g] -= self.weights[i].1;
+            }
+        }
+    }
+
+    let k = k.max(1);
+    let weights = weights(inventory);
+    let sum = weights.iter().map(|w| w.1).sum::<u64>();
+
+    // Seed the bound with the heuristic answer so only improvements are explored.
+    let seed = greedy(inventory, k);
+    let mut best = vec![0; weights.len()];
+    for (g, group) in seed.groups.iter().enumerate() {
+        for id in &group.members {
+            let i = weights.iter().position(|w| w.0 == *id).unwrap();
+            best[i] = g;
+        }
+    }
+
+    let mut search = Search {
+        floor: sum / k as u64,
+        perfect: if sum % k as u64 == 0 { 0 } else { 1 },
+        totals: vec![0; k],
+        assignment: vec![0; weights.len()],
+        best_spread: seed.spread(),
+        best,
+        weights,
+        nodes: 0,
+    };
+    search.run(0);
+    if search.nodes > NODE_BUDGET {
+        return None;
+    }
+
+    let mut groups = vec![Group::default(); k];
+    for (&(id, total), &g) in search.weights.iter().zip(&search.best) {
+        groups[g].add(id, total);
+    }
+    Some(Plan::new(Method::Exact, groups))
+}
+
+impl fmt::Display for Plan {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let method = match self.method {
+            Method::Greedy => "greedy",
+            Method::KarmarkarKarp => "karmarkar-karp",
+            Method::Exact => "exact",
+        };
+        writeln!(f, "{} plan for {} groups", method, self.groups.len())?;
+        for (i, group) in self.groups.iter().enumerate() {
+            let members = group
+                .members
+                .iter()
+                .map(|id| format!("#{}", id))
+                .collect::<Vec<_>>()
+                .join(", ");
+            writeln!(
+                f,
+                "  group {}: {} calories from {} elves: {}",
+                i + 1,
+                thousands(group.total),
+                group.members.len(),
+                members
+            )?;
+        }
+        writeln!(f, "spread: {}", thousands(self.spread()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Mode;
+
+    fn inventory(totals: &[u64]) -> Inventory {
+        let input = totals
+            .iter()
+            .map(|t| t.to_string())
+            .collect::<Vec<_>>()
+            .join("\n\n");
+        Inventory::parse(&input, Mode::Strict).unwrap()
+    }
+
+    fn check(plan: &Plan, inventory: &Inventory) {
+        let mut members = plan
+            .groups
+            .iter()
+            .flat_map(|g| g.members.iter().copied())
+            .collect::<Vec<_>>();
+        members.sort_unstable();
+        assert_eq!((1..=inventory.elves.len()).collect::<Vec<_>>(), members);
+        for group in &plan.groups {
+            let total = group.members.iter().map(|&id| inventory.elf(id).unwrap().total()).sum::<u64>();
+            assert_eq!(total, group.total);
+        }
+    }
+
+    #[test]
+    fn test_methods() {
+        let inventory = inventory(&[8, 7, 6, 5, 4]);
+
+        let greedy = greedy(&inventory, 2);
+        check(&greedy, &inventory);
+        assert_eq!(4, greedy.spread());
+
+        let kk = karmarkar_karp(&inventory, 2);
+        check(&kk, &inventory);
+        assert_eq!(2, kk.spread());
+
+        let exact = exact(&inventory, 2).unwrap();
+        check(&exact, &inventory);
+        assert_eq!(0, exact.spread());
+        assert_eq!(vec![vec![1, 2], vec![3, 4, 5]], exact.groups.iter().map(|g| g.members.clone()).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_exact_three_way() {
+        let inventory = inventory(&[6000, 4000, 11000, 24000, 10000]);
+        let plan = exact(&inventory, 3).unwrap();
+        check(&plan, &inventory);
+        assert_eq!(vec![24000, 16000, 15000], plan.groups.iter().map(|g| g.total).collect::<Vec<_>>());
+        assert_eq!(9000, plan.spread());
+    }
+
+    #[test]
+    fn test_more_groups_than_elves() {
+        let inventory = inventory(&[3, 2]);
+        for plan in [greedy(&inventory, 3), karmarkar_karp(&inventory, 3), exact(&inventory, 3).unwrap()] {
+            check(&plan, &inventory);
+            assert_eq!(3, plan.groups.len());
+            assert_eq!(3, plan.spread());
+        }
+    }
+
+    #[test]
+    fn test_exact_refuses_large_inventories() {
+        let mut totals = vec![0; EXACT_LIMIT + 1];
+        totals[..5].copy_from_slice(&[8, 7, 6, 5, 4]);
+        let inventory = inventory(&totals);
+        assert_eq!(None, exact(&inventory, 2));
+        assert_eq!(Method::KarmarkarKarp, plan(&inventory, 2).method);
+    }
+
+    #[test]
+    fn test_exact_gives_up_with_many_groups() {
+        let totals = (1..=EXACT_LIMIT as u64).map(|i| i * i * 7919 % 10007 + 1000).collect::<Vec<_>>();
+        let inventory = inventory(&totals);
+        assert_eq!(None, exact(&inventory, 8));
+        let plan = plan(&inventory, 8);
+        check(&plan, &inventory);
+        assert_ne!(Method::Exact, plan.method);
+    }
+}
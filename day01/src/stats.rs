@@ -0,0 +1,185 @@
+use std::collections::BTreeMap;
+use std::fmt;
+
+use crate::{thousands, Inventory};
+
+const PERCENTILES: [u32; 5] = [10, 25, 75, 90, 99];
+const BAR_WIDTH: usize = 50;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Bin {
+    pub low: u64,
+    pub high: u64,
+    pub count: usize,
+}
+
+/// Distribution of calorie totals across all elves of an inventory.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Stats {
+    pub count: usize,
+    pub min: u64,
+    pub max: u64,
+    pub mean: f64,
+    pub median: f64,
+    pub std_dev: f64,
+    pub percentiles: Vec<(u32, u64)>,
+    /// Number of elves carrying a given number of items.
+    pub item_counts: BTreeMap<usize, usize>,
+    pub histogram: Vec<Bin>,
+}
+
+impl Stats {
+    /// Returns `None` for an inventory without elves.
+    pub fn new(inventory: &Inventory, bins: usize) -> Option<Self> {
+        let mut totals = inventory.elves.iter().map(|e| e.total()).collect::<Vec<_>>();
+        totals.sort_unstable();
+
+        let count = totals.len();
+        let (&min, &max) = (totals.first()?, totals.last()?);
+        let mean = totals.iter().map(|&t| t as f64).sum::<f64>() / count as f64;
+        let median = if count % 2 == 0 {
+            (totals[count / 2 - 1] as f64 + totals[count / 2] as f64) / 2.0
+        } else {
+            totals[count / 2] as f64
+        };
+        let variance = totals
+            .iter()
+            .map(|&t| (t as f64 - mean).powi(2))
+            .sum::<f64>()
+            / count as f64;
+
+        let mut item_counts = BTreeMap::new();
+        for elf in &inventory.elves {
+            *item_counts.entry(elf.items.len()).or_insert(0) += 1;
+        }
+
+        Some(Stats {
+            count,
+            min,
+            max,
+            mean,
+            median,
+            std_dev: variance.sqrt(),
+            percentiles: PERCENTILES
+                .iter()
+                .map(|&p| (p, percentile(&totals, p)))
+                .collect(),
+            item_counts,
+            histogram: histogram(&totals, bins.max(1)),
+        })
+    }
+}
+
+/// Nearest-rank percentile of an ascending, non-empty slice.
+fn percentile(sorted: &[u64], p: u32) -> u64 {
+    let rank = (p as usize * sorted.len()).div_ceil(100);
+    sorted[rank.saturating_sub(1)]
+}
+
+/// Splits `[min, max]` of an ascending, non-empty slice into at most `bins`
+/// equal-width buckets; the last bucket ends at `max`, so it may be narrower.
+fn histogram(sorted: &[u64], bins: usize) -> Vec<Bin> {
+    let (min, max) = (sorted[0], sorted[sorted.len() - 1]);
+    // Round up so the buckets cover every value without the last one
+    // absorbing the remainder.
+    let width = (max - min).saturating_add(1).div_ceil(bins as u64);
+    let bins = (((max - min) / width) as usize + 1).min(bins);
+
+    let mut histogram = (0..bins)
+        .map(|i| Bin {
+            low: min + width * i as u64,
+            high: if i + 1 == bins { max } else { min + width * (i as u64 + 1) - 1 },
+            count: 0,
+        })
+        .collect::<Vec<_>>();
+    for &total in sorted {
+        let i = (((total - min) / width) as usize).min(bins - 1);
+        histogram[i].count += 1;
+    }
+    histogram
+}
+
+impl fmt::Display for Stats {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "elves:   {}", self.count)?;
+        writeln!(f, "min:     {}", thousands(self.min))?;
+        writeln!(f, "max:     {}", thousands(self.max))?;
+        writeln!(f, "mean:    {:.1}", self.mean)?;
+        writeln!(f, "median:  {:.1}", self.median)?;
+        writeln!(f, "std dev: {:.1}", self.std_dev)?;
+        for (p, value) in &self.percentiles {
+            writeln!(f, "p{:<6} {}", format!("{}:", p), thousands(*value))?;
+        }
+
+        writeln!(f, "\nitems per elf:")?;
+        for (items, elves) in &self.item_counts {
+            writeln!(f, "  {:>3} items: {} elves", items, elves)?;
+        }
+
+        writeln!(f, "\ncalorie totals:")?;
+        let tallest = self.histogram.iter().map(|b| b.count).max().unwrap_or(0).max(1);
+        let label = self
+            .histogram
+            .iter()
+            .map(|b| format!("{} - {}", thousands(b.low), thousands(b.high)))
+            .collect::<Vec<_>>();
+        let label_width = label.iter().map(String::len).max().unwrap_or(0);
+        for (bin, label) in self.histogram.iter().zip(label) {
+            let bar = (bin.count * BAR_WIDTH).div_ceil(tallest);
+            writeln!(
+                f,
+                "  {:>w$} | {:<4} {}",
+                label,
+                bin.count,
+                "#".repeat(bar),
+                w = label_width
+            )?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{tests::INPUT, Mode};
+
+    #[test]
+    fn test_stats() {
+        let inventory = Inventory::parse(INPUT, Mode::Strict).unwrap();
+        let stats = Stats::new(&inventory, 4).unwrap();
+
+        assert_eq!(5, stats.count);
+        assert_eq!((4000, 24000), (stats.min, stats.max));
+        assert_eq!(11000.0, stats.mean);
+        assert_eq!(10000.0, stats.median);
+        assert_eq!(6985.7, (stats.std_dev * 10.0).round() / 10.0);
+        assert_eq!(vec![(10, 4000), (25, 6000), (75, 11000), (90, 24000), (99, 24000)], stats.percentiles);
+        assert_eq!(vec![(1, 2), (2, 1), (3, 2)], stats.item_counts.into_iter().collect::<Vec<_>>());
+        assert_eq!(
+            vec![
+                Bin { low: 4000, high: 9000, count: 2 },
+                Bin { low: 9001, high: 14001, count: 2 },
+                Bin { low: 14002, high: 19002, count: 0 },
+                Bin { low: 19003, high: 24000, count: 1 },
+            ],
+            stats.histogram
+        );
+    }
+
+    #[test]
+    fn test_stats_edge_cases() {
+        assert_eq!(None, Stats::new(&Inventory::parse("", Mode::Strict).unwrap(), 10));
+
+        let inventory = Inventory::parse("5\n\n5\n\n7", Mode::Strict).unwrap();
+        let stats = Stats::new(&inventory, 10).unwrap();
+        assert_eq!(5.0, stats.median);
+        assert_eq!(3, stats.histogram.len());
+        assert_eq!(3, stats.histogram.iter().map(|b| b.count).sum::<usize>());
+
+        let input = (1..=20).map(|n| n.to_string()).collect::<Vec<_>>().join("\n\n");
+        let stats = Stats::new(&Inventory::parse(&input, Mode::Strict).unwrap(), 10).unwrap();
+        assert_eq!(10, stats.histogram.len());
+        assert!(stats.histogram.iter().all(|b| b.high - b.low == 1 && b.count == 2));
+    }
+}
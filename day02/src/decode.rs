@@ -0,0 +1,195 @@
+use std::error::Error;
+use std::fmt;
+
+use crate::{play_moves, play_outcomes, Game, OUTCOMES};
+
+/// One reading of the guide's second column: what each symbol stands for.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Decoding {
+    /// Meaning of each symbol, in the order of [`Decodings::symbols`].
+    pub meanings: Vec<String>,
+    pub score: i64,
+}
+
+/// Every way the second column could be read, highest score first.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Decodings {
+    pub symbols: Vec<String>,
+    pub decodings: Vec<Decoding>,
+}
+
+impl Decodings {
+    fn new(symbols: Vec<String>, mut decodings: Vec<Decoding>) -> Self {
+        decodings.sort_by_key(|d| std::cmp::Reverse(d.score));
+        Decodings { symbols, decodings }
+    }
+
+    /// The highest scoring decodings, including ties.
+    pub fn best(&self) -> Vec<&Decoding> {
+        let best = self.decodings.first().map(|d| d.score);
+        self.decodings.iter().filter(|d| Some(d.score) == best).collect()
+    }
+
+    /// The lowest scoring decodings, including ties.
+    pub fn worst(&self) -> Vec<&Decoding> {
+        let worst = self.decodings.last().map(|d| d.score);
+        self.decodings.iter().filter(|d| Some(d.score) == worst).collect()
+    }
+
+    pub fn matching(&self, total: i64) -> Vec<&Decoding> {
+        self.decodings.iter().filter(|d| d.score == total).collect()
+    }
+
+    pub fn describe(&self, decoding: &Decoding) -> String {
+        self.symbols
+            .iter()
+            .zip(&decoding.meanings)
+            .map(|(symbol, meaning)| format!("{}={}", symbol, meaning))
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+}
+
+/// Games with more moves have too many readings to score them all: 9 moves
+/// already give 362,880.
+pub const MAX_DECODED_MOVES: usize = 8;
+
+/// The game has more moves than [`MAX_DECODED_MOVES`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TooManyMoves(pub usize);
+
+impl fmt::Display for TooManyMoves {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "decoding supports at most {} moves, the game has {}", MAX_DECODED_MOVES, self.0)
+    }
+}
+
+impl Error for TooManyMoves {}
+
+/// Scores the guide under every assignment of your symbols to moves.
+pub fn moves(game: &Game, input: &str) -> Result<Decodings, TooManyMoves> {
+    if game.len() > MAX_DECODED_MOVES {
+        return Err(TooManyMoves(game.len()));
+    }
+    let decodings = permutations(game.len())
+        .into_iter()
+        .map(|order| {
+            // Symbol `i` of the guide stands for move `order[i]`.
+            let mut you_symbols = vec![String::new(); game.len()];
+            for (symbol, &m) in game.you_symbols.iter().zip(&order) {
+                you_symbols[m] = symbol.clone();
+            }
+            let decoded = Game { you_symbols, ..game.clone() };
+            Decoding {
+                meanings: order.iter().map(|&m| game.moves[m].clone()).collect(),
+                score: play_moves(&decoded, input),
+            }
+        })
+        .collect();
+
+    Ok(Decodings::new(game.you_symbols.clone(), decodings))
+}
+
+/// Scores the guide under every assignment of the outcome symbols to outcomes.
+pub fn outcomes(game: &Game, input: &str) -> Decodings {
+    let decodings = permutations(OUTCOMES.len())
+        .into_iter()
+        .map(|order| {
+            let mut outcome_symbols = game.outcome_symbols.clone();
+            for (symbol, &o) in game.outcome_symbols.iter().zip(&order) {
+                outcome_symbols[o] = symbol.clone();
+            }
+            let decoded = Game { outcome_symbols, ..game.clone() };
+            Decoding {
+                meanings: order.iter().map(|&o| format!("{:?}", OUTCOMES[o])).collect(),
+                score: play_outcomes(&decoded, input),
+            }
+        })
+        .collect();
+
+    Decodings::new(game.outcome_symbols.to_vec(), decodings)
+}
+
+/// All orderings of `0..n` in lexicographic order, starting with the identity.
+fn permutations(n: usize) -> Vec<Vec<usize>> {
+    fn extend(prefix: &mut Vec<usize>, n: usize, out: &mut Vec<Vec<usize>>) {
+        if prefix.len() == n {
+            out.push(prefix.clone());
+            return;
+        }
+        for i in 0..n {
+            if !prefix.contains(&i) {
+                prefix.push(i);
+                extend(prefix, n, out);
+                prefix.pop();
+            }
+        }
+    }
+
+    let mut out = Vec::new();
+    extend(&mut Vec::with_capacity(n), n, &mut out);
+    out
+}
+
+impl fmt::Display for Decodings {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (label, decodings) in [("best", self.best()), ("worst", self.worst())] {
+            for decoding in decodings {
+                writeln!(f, "  {:<6} {:>8}  {}", label, decoding.score, self.describe(decoding))?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::INPUT;
+
+    #[test]
+    fn test_permutations() {
+        assert_eq!(
+            vec![vec![0, 1, 2], vec![0, 2, 1], vec![1, 0, 2], vec![1, 2, 0], vec![2, 0, 1], vec![2, 1, 0]],
+            permutations(3)
+        );
+        assert_eq!(120, permutations(5).len());
+    }
+
+    #[test]
+    fn test_move_decodings() {
+        let game = Game::rps();
+        let decodings = moves(&game, INPUT).unwrap();
+        assert_eq!(6, decodings.decodings.len());
+
+        let identity = vec!["Rock".to_string(), "Paper".into(), "Scissors".into()];
+        assert!(decodings.matching(15).iter().any(|d| d.meanings == identity));
+
+        // The only reading where every round is won.
+        let best = decodings.best();
+        assert_eq!(1, best.len());
+        assert_eq!(8 + 9 + 7, best[0].score);
+        assert_eq!("X=Scissors Y=Paper Z=Rock", decodings.describe(best[0]));
+        assert_eq!(vec![6], decodings.worst().iter().map(|d| d.score).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_too_many_moves() {
+        let names = (0..9).map(|i| i.to_string()).collect::<Vec<_>>();
+        let game = Game::new(&names.iter().map(String::as_str).collect::<Vec<_>>()).unwrap();
+        assert_eq!(Err(TooManyMoves(9)), moves(&game, ""));
+        assert!(moves(&Game::rpsls(), INPUT).is_ok());
+    }
+
+    #[test]
+    fn test_outcome_decodings() {
+        let game = Game::rps();
+        let decodings = outcomes(&game, INPUT);
+        assert_eq!(6, decodings.decodings.len());
+
+        let puzzle = decodings.matching(12);
+        assert!(puzzle.iter().any(|d| decodings.describe(d) == "X=Lose Y=Draw Z=Win"));
+        assert!(decodings.best().iter().all(|d| d.score == 18));
+        assert!(decodings.worst().iter().all(|d| d.score == 12));
+    }
+}
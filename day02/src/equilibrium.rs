@@ -0,0 +1,244 @@
+use std::fmt;
+
+use crate::{play_moves, play_outcomes, Game, Move, PlayerMove};
+
+const EPSILON: f64 = 1e-9;
+
+/// How the strategy guide compares with game-theoretically optimal play.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Analysis {
+    pub moves: Vec<String>,
+    /// `payoff[i][j]`: your score for playing move `i` against the elf's move `j`.
+    pub payoff: Vec<Vec<f64>>,
+    /// The maximin mixed strategy: the mix of your moves that guarantees the
+    /// highest expected score per round whatever the elf plays.
+    pub strategy: Vec<f64>,
+    /// Expected score per round of `strategy` against an adversarial elf.
+    pub value: f64,
+    /// How often the elf plays each move in the guide.
+    pub opponent: Vec<f64>,
+    /// Expected score per round of `strategy` against `opponent`.
+    pub against_opponent: f64,
+    /// The pure move scoring best against `opponent`, and its expected score.
+    pub best_response: (Move, f64),
+    pub rounds: usize,
+    /// Average score per round of the guide read as moves and as outcomes.
+    pub guide_moves: f64,
+    pub guide_outcomes: f64,
+}
+
+pub fn payoff(game: &Game) -> Vec<Vec<f64>> {
+    (0..game.len())
+        .map(Move)
+        .map(|you| {
+            (0..game.len())
+                .map(Move)
+                .map(|elf| PlayerMove::You(you).outcome(&PlayerMove::Elf(elf), game) as f64)
+                .collect()
+        })
+        .collect()
+}
+
+/// How often the elf plays each move in the guide, and the number of rounds.
+pub fn opponent(game: &Game, input: &str) -> (Vec<f64>, usize) {
+    let mut counts = vec![0usize; game.len()];
+    for line in input.lines() {
+        let symbol = line.split(' ').next().unwrap_or("");
+        match game.elf_move(symbol).expect("Error parsing elf move") {
+            PlayerMove::Elf(m) => counts[m.0] += 1,
+            PlayerMove::You(_) => unreachable!(),
+        }
+    }
+    let rounds = counts.iter().sum::<usize>();
+    let frequencies = counts.iter().map(|&c| c as f64 / rounds.max(1) as f64).collect();
+    (frequencies, rounds)
+}
+
+/// Returns `None` for a guide without rounds.
+pub fn analyse(game: &Game, input: &str) -> Option<Analysis> {
+    let (opponent, rounds) = opponent(game, input);
+    if rounds == 0 {
+        return None;
+    }
+
+    let payoff = payoff(game);
+    let (strategy, value) = solve(&payoff);
+    let against_opponent = strategy.iter().enumerate().map(|(i, x)| x * expected(&payoff, &opponent, i)).sum();
+    let best_response = best_response(&payoff, &opponent);
+
+    Some(Analysis {
+        moves: game.moves.clone(),
+        payoff,
+        strategy,
+        value,
+        opponent,
+        against_opponent,
+        best_response,
+        rounds,
+        guide_moves: play_moves(game, input) as f64 / rounds as f64,
+        guide_outcomes: play_outcomes(game, input) as f64 / rounds as f64,
+    })
+}
+
+/// Your expected score per round playing move `i` against an elf mixing its
+/// moves as `opponent`.
+fn expected(payoff: &[Vec<f64>], opponent: &[f64], i: usize) -> f64 {
+    payoff[i].iter().zip(opponent).map(|(a, p)| a * p).sum()
+}
+
+/// The pure move scoring best against `opponent`, and its expected score.
+/// Moves within `EPSILON` of each other count as tied and the first wins.
+pub fn best_response(payoff: &[Vec<f64>], opponent: &[f64]) -> (Move, f64) {
+    (0..payoff.len())
+        .map(|i| (Move(i), expected(payoff, opponent, i)))
+        .fold((Move(0), f64::MIN), |best, r| if r.1 > best.1 + EPSILON { r } else { best })
+}
+
+/// Solves the zero-sum game where you pick a row of `payoff` to maximise and
+/// the elf picks a column to minimise, returning your optimal mix and the
+/// value of the game.
+///
+/// After shifting every payoff to be positive, the elf's problem becomes the
+/// linear program `max 1·w` subject to `A w <= 1, w >= 0`, whose optimum is
+/// `1 / value`. Its starting tableau is feasible at the origin, so a plain
+/// simplex with Bland's rule solves it, and your strategy can be read off
+/// the dual prices of the slack columns.
+pub fn solve(payoff: &[Vec<f64>]) -> (Vec<f64>, f64) {
+    let m = payoff.len();
+    let n = payoff[0].len();
+    let min = payoff.iter().flatten().copied().fold(f64::INFINITY, f64::min);
+    let shift = 1.0 - min;
+
+    // Rows: one constraint per row of the payoff matrix, then the objective.
+    // Columns: w_0..w_n, slack_0..slack_m, right hand side.
+    let width = n + m + 1;
+    let mut tableau = vec![vec![0.0; width]; m + 1];
+    for (i, (row, payoffs)) in tableau.iter_mut().zip(payoff).enumerate() {
+        for (t, p) in row.iter_mut().zip(payoffs) {
+            *t = p + shift;
+        }
+        row[n + i] = 1.0;
+        row[width - 1] = 1.0;
+    }
+    tableau[m][..n].fill(-1.0);
+    let mut basis = (n..n + m).collect::<Vec<_>>();
+
+    while let Some(col) = (0..width - 1).find(|&c| tableau[m][c] < -EPSILON) {
+        let row = (0..m)
+            .filter(|&r| tableau[r][col] > EPSILON)
+            .min_by(|&a, &b| {
+                let ra = tableau[a][width - 1] / tableau[a][col];
+                let rb = tableau[b][width - 1] / tableau[b][col];
+                ra.partial_cmp(&rb).unwrap().then(basis[a].cmp(&basis[b]))
+            })
+            .expect("bounded: every column has a positive entry");
+
+        let pivot = tableau[row][col];
+        for v in tableau[row].iter_mut() {
+            *v /= pivot;
+        }
+        let pivot_row = tableau[row].clone();
+        for (r, other) in tableau.iter_mut().enumerate() {
+            let factor = other[col];
+            if r != row && factor.abs() > EPSILON {
+                for (v, p) in other.iter_mut().zip(&pivot_row) {
+                    *v -= factor * p;
+                }
+            }
+        }
+        basis[row] = col;
+    }
+
+    let total = tableau[m][width - 1];
+    let strategy = (0..m).map(|i| tableau[m][n + i] / total).collect();
+    (strategy, 1.0 / total - shift)
+}
+
+impl fmt::Display for Analysis {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "{:<10} {:>8} {:>8}", "move", "optimal", "elf")?;
+        for (i, name) in self.moves.iter().enumerate() {
+            writeln!(
+                f,
+                "{:<10} {:>7.1}% {:>7.1}%",
+                name,
+                self.strategy[i] * 100.0,
+                self.opponent[i] * 100.0
+            )?;
+        }
+        writeln!(f)?;
+        writeln!(f, "game value:                     {:.3} per round", self.value)?;
+        writeln!(f, "optimal mix vs the guide's elf: {:.3} per round", self.against_opponent)?;
+        writeln!(
+            f,
+            "best response vs the elf:       {:.3} per round, always {}",
+            self.best_response.1,
+            self.moves[self.best_response.0 .0]
+        )?;
+        writeln!(f)?;
+        for (reading, average) in [("moves", self.guide_moves), ("outcomes", self.guide_outcomes)] {
+            writeln!(
+                f,
+                "guide read as {:<9} {:.3} per round, {:+.3} vs the optimal mix over {} rounds",
+                format!("{}:", reading),
+                average,
+                average - self.against_opponent,
+                self.rounds
+            )?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_close(expected: f64, actual: f64) {
+        assert!((expected - actual).abs() < 1e-6, "expected {}, got {}", expected, actual);
+    }
+
+    #[test]
+    fn test_rps_equilibrium_is_uniform() {
+        let game = Game::rps();
+        assert_eq!(vec![vec![4.0, 1.0, 7.0], vec![8.0, 5.0, 2.0], vec![3.0, 9.0, 6.0]], payoff(&game));
+
+        let (strategy, value) = solve(&payoff(&game));
+        for x in strategy {
+            assert_close(1.0 / 3.0, x);
+        }
+        assert_close(5.0, value);
+    }
+
+    #[test]
+    fn test_solve_matching_pennies_and_dominated_moves() {
+        let (strategy, value) = solve(&[vec![1.0, -1.0], vec![-1.0, 1.0]]);
+        assert_close(0.5, strategy[0]);
+        assert_close(0.0, value);
+
+        // The second row is dominated and must never be played.
+        let (strategy, value) = solve(&[vec![3.0, 1.0], vec![2.0, 0.0], vec![0.0, 2.0]]);
+        assert_close(0.0, strategy[1]);
+        assert_close(0.5, strategy[0]);
+        assert_close(1.5, value);
+    }
+
+    #[test]
+    fn test_analysis() {
+        let game = Game::rps();
+        let analysis = analyse(&game, "A Y\nA X\nB Z\nC Z").unwrap();
+
+        assert_eq!(vec![0.5, 0.25, 0.25], analysis.opponent);
+        assert_close(5.0, analysis.against_opponent);
+        // Against mostly Rock, Paper averages (8 * 2 + 5 + 2) / 4.
+        assert_eq!(Move(1), analysis.best_response.0);
+        assert_close(5.75, analysis.best_response.1);
+        assert_close((8.0 + 4.0 + 9.0 + 6.0) / 4.0, analysis.guide_moves);
+        assert_close((4.0 + 3.0 + 9.0 + 7.0) / 4.0, analysis.guide_outcomes);
+
+        assert_eq!(None, analyse(&game, ""));
+
+        // Paper and Scissors both average 57 / 9 here; the first is kept.
+        assert_eq!(Move(1), best_response(&payoff(&game), &[4.0 / 9.0, 5.0 / 9.0, 0.0]).0);
+    }
+}
@@ -0,0 +1,95 @@
+use std::io::{self, Write};
+
+use crate::{read_moves, read_outcomes, Game, PlayerMove};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Reading {
+    Moves,
+    Outcomes,
+}
+
+impl Reading {
+    fn name(&self) -> &'static str {
+        match self {
+            Self::Moves => "moves",
+            Self::Outcomes => "outcomes",
+        }
+    }
+}
+
+/// Writes one CSV row per round of the guide under each of `readings`, with
+/// the points the round earned and the running total for that reading.
+pub fn write_csv(out: &mut impl Write, game: &Game, input: &str, readings: &[Reading]) -> io::Result<()> {
+    writeln!(out, "reading,round,opponent,you,outcome,shape_points,outcome_points,running_total")?;
+    for &reading in readings {
+        let rounds: Box<dyn Iterator<Item = Vec<PlayerMove>>> = match reading {
+            Reading::Moves => Box::new(read_moves(game, input)),
+            Reading::Outcomes => Box::new(read_outcomes(game, input)),
+        };
+
+        let mut total = 0;
+        for (i, round) in rounds.enumerate() {
+            let (elf, you) = match (round[0], round[1]) {
+                (PlayerMove::Elf(elf), PlayerMove::You(you)) => (elf, you),
+                _ => unreachable!("rounds are read as the elf's move, then yours"),
+            };
+            let outcome = game.outcome(you, elf);
+            let shape_points = game.shape_score(you);
+            let outcome_points = game.outcome_score(outcome);
+            total += shape_points + outcome_points;
+
+            writeln!(
+                out,
+                "{},{},{},{},{:?},{},{},{}",
+                reading.name(),
+                i + 1,
+                field(&game.moves[elf.0]),
+                field(&game.moves[you.0]),
+                outcome,
+                shape_points,
+                outcome_points,
+                total
+            )?;
+        }
+    }
+    Ok(())
+}
+
+/// Quotes a field when it would otherwise break the row apart.
+fn field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::INPUT;
+
+    #[test]
+    fn test_write_csv() {
+        let mut out = Vec::new();
+        write_csv(&mut out, &Game::rps(), INPUT, &[Reading::Moves, Reading::Outcomes]).unwrap();
+        assert_eq!(
+            "reading,round,opponent,you,outcome,shape_points,outcome_points,running_total
+moves,1,Rock,Paper,Win,2,6,8
+moves,2,Paper,Rock,Lose,1,0,9
+moves,3,Scissors,Scissors,Draw,3,3,15
+outcomes,1,Rock,Rock,Draw,1,3,4
+outcomes,2,Paper,Rock,Lose,1,0,5
+outcomes,3,Scissors,Rock,Win,1,6,12
+",
+            String::from_utf8(out).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_fields_are_quoted() {
+        assert_eq!("Rock", field("Rock"));
+        assert_eq!("\"Rock, Hard\"", field("Rock, Hard"));
+        assert_eq!("\"The \"\"Rock\"\"\"", field("The \"Rock\""));
+    }
+}
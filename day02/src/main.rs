@@ -1,182 +1,527 @@
-use std::str::FromStr;
+use std::error::Error;
+use std::fmt;
+use std::io::Write;
 use std::{env, fs, io, path::Path};
 
+mod decode;
+mod equilibrium;
+mod export;
+mod rules;
+mod simulate;
+
+use rules::Rules;
+
 fn main() -> io::Result<()> {
     let mut dir = env::current_exe()?;
     dir.pop();
     let dir = dir.join(Path::new("../../day02/input.txt"));
 
-    let input = fs::read_to_string(dir)?;
-    println!("part1: {}", part1(&input));
-    println!("part2: {}", part2(&input));
+    let (flags, args): (Vec<String>, Vec<String>) = env::args().skip(1).partition(|a| a.starts_with("--"));
+    let path = flags
+        .iter()
+        .find_map(|f| f.strip_prefix("--input="))
+        .map_or(dir, |p| p.into());
+    let game = match (
+        flags.iter().find_map(|f| f.strip_prefix("--rules=")),
+        flags.iter().find_map(|f| f.strip_prefix("--game=")),
+    ) {
+        (Some(rules), _) => {
+            let text = fs::read_to_string(rules)?;
+            if rules.ends_with(".json") {
+                Rules::from_json(&text)
+            } else {
+                Rules::from_toml(&text)
+            }
+            .and_then(Game::with_rules)
+        }
+        (None, None | Some("rps")) => Ok(Game::rps()),
+        (None, Some("rpsls")) => Ok(Game::rpsls()),
+        (None, Some(moves)) => Game::new(&moves.split(',').collect::<Vec<_>>()),
+    }
+    .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+
+    let input = fs::read_to_string(path)?;
+    match args.first().map(String::as_str) {
+        Some("decodings") => {
+            let claimed = args.get(1).and_then(|t| t.parse::<i64>().ok());
+            let moves = decode::moves(&game, &input).map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+            for (reading, decodings) in [
+                ("your move", moves),
+                ("the outcome", decode::outcomes(&game, &input)),
+            ] {
+                println!("{} as {}:", decodings.symbols.join("/"), reading);
+                print!("{}", decodings);
+                if let Some(claimed) = claimed {
+                    for decoding in decodings.matching(claimed) {
+                        println!("  {:<6} {:>8}  {}", "claim", decoding.score, decodings.describe(decoding));
+                    }
+                }
+            }
+        }
+        Some("equilibrium") => match equilibrium::analyse(&game, &input) {
+            Some(analysis) => print!("{}", analysis),
+            None => println!("empty strategy guide"),
+        },
+        Some("export") => {
+            let readings = match args.get(1).map(String::as_str) {
+                Some("moves") => vec![export::Reading::Moves],
+                Some("outcomes") => vec![export::Reading::Outcomes],
+                None | Some("both") => vec![export::Reading::Moves, export::Reading::Outcomes],
+                Some(other) => {
+                    let usage = format!("unknown reading {:?}, expected moves, outcomes or both", other);
+                    return Err(io::Error::new(io::ErrorKind::InvalidInput, usage));
+                }
+            };
+            match args.get(2) {
+                Some(file) => {
+                    let mut out = io::BufWriter::new(fs::File::create(file)?);
+                    export::write_csv(&mut out, &game, &input, &readings)?;
+                    // Dropping a BufWriter ignores a failed final write.
+                    out.flush()?;
+                }
+                None => export::write_csv(&mut io::stdout().lock(), &game, &input, &readings)?,
+            }
+        }
+        Some("simulate") => {
+            let tournaments = args.get(1).and_then(|t| t.parse().ok()).unwrap_or(1000);
+            let seed = args.get(2).and_then(|s| s.parse().ok()).unwrap_or(2022);
+            let (opponent, rounds) = equilibrium::opponent(&game, &input);
+            let strategies = simulate::strategies(&game, &input);
+            let summaries = simulate::simulate(&game, &opponent, &strategies, tournaments, rounds, seed);
+            println!("{} tournaments of {} rounds, seed {}", tournaments, rounds, seed);
+            print!("{}", simulate::Report(&summaries));
+        }
+        _ => {
+            println!("part1: {}", part1(&game, &input));
+            println!("part2: {}", part2(&game, &input));
+        }
+    }
     Ok(())
 }
 
-#[derive(Debug)]
-enum Move {
-    Rock,
-    Paper,
-    Scissors,
-}
+/// A move, identified by its position on the game's ring of moves.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Move(usize);
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 enum PlayerMove {
     Elf(Move),
     You(Move),
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 enum Outcome {
     Win,
     Lose,
     Draw,
 }
 
-impl FromStr for PlayerMove {
-    type Err = ();
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum GameError {
+    TooFewMoves(usize),
+    TooManyMoves(usize),
+    EvenMoves(usize),
+    DuplicateMove(String),
+    SymbolCount { player: &'static str, expected: usize, got: usize },
+    ScoreCount { expected: usize, got: usize },
+    DuplicateSymbol(String),
+    InvalidSymbol(String),
+    InvalidRules(String),
+}
 
-    fn from_str(input: &str) -> Result<Self, Self::Err> {
-        match input {
-            "A" => Ok(Self::Elf(Move::Rock)),
-            "B" => Ok(Self::Elf(Move::Paper)),
-            "C" => Ok(Self::Elf(Move::Scissors)),
-            "X" => Ok(Self::You(Move::Rock)),
-            "Y" => Ok(Self::You(Move::Paper)),
-            "Z" => Ok(Self::You(Move::Scissors)),
-            _ => Err(()),
+impl fmt::Display for GameError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::TooFewMoves(n) => write!(f, "a game needs at least 3 moves, got {}", n),
+            Self::TooManyMoves(n) => write!(
+                f,
+                "games with more than {} moves need explicit symbols, got {} moves",
+                MAX_MOVES, n
+            ),
+            Self::EvenMoves(n) => write!(f, "a cyclic game needs an odd number of moves, got {}", n),
+            Self::DuplicateMove(name) => write!(f, "move {:?} appears more than once", name),
+            Self::SymbolCount { player, expected, got } => {
+                write!(f, "expected {} {} symbols, got {}", expected, player, got)
+            }
+            Self::ScoreCount { expected, got } => {
+                write!(f, "expected {} shape scores, got {}", expected, got)
+            }
+            Self::DuplicateSymbol(symbol) => write!(f, "symbol {:?} is used more than once", symbol),
+            Self::InvalidSymbol(symbol) => {
+                write!(f, "symbol {:?} must be non-empty and contain no whitespace", symbol)
+            }
+            Self::InvalidRules(e) => write!(f, "invalid rules: {}", e),
         }
     }
 }
 
-impl FromStr for Outcome {
-    type Err = ();
+impl Error for GameError {}
+
+/// Default symbols letter the elf's moves from `A` and yours up to `Z`, so the
+/// two alphabets stop overlapping at 13 moves.
+const MAX_MOVES: usize = 13;
 
-    fn from_str(input: &str) -> Result<Self, Self::Err> {
-        match input {
-            "X" => Ok(Self::Lose),
-            "Y" => Ok(Self::Draw),
-            "Z" => Ok(Self::Win),
-            _ => Err(()),
+const OUTCOMES: [Outcome; 3] = [Outcome::Lose, Outcome::Draw, Outcome::Win];
+
+impl Outcome {
+    fn index(&self) -> usize {
+        match self {
+            Self::Lose => 0,
+            Self::Draw => 1,
+            Self::Win => 2,
         }
     }
 }
 
-impl Move {
-    fn given(elf_move: &Move, outcome: &Outcome) -> Move {
-        match outcome {
-            Outcome::Win => match elf_move {
-                Self::Rock => Self::Paper,
-                Self::Paper => Self::Scissors,
-                Self::Scissors => Self::Rock,
-            },
-            Outcome::Lose => match elf_move {
-                Self::Rock => Self::Scissors,
-                Self::Paper => Self::Rock,
-                Self::Scissors => Self::Paper,
-            },
-            Outcome::Draw => match elf_move {
-                Self::Rock => Self::Rock,
-                Self::Paper => Self::Paper,
-                Self::Scissors => Self::Scissors,
-            },
-        }
+/// A cyclic dominance game over an odd number of moves arranged on a ring.
+/// Every move beats the moves an odd number of steps behind it and loses to
+/// the ones an even number of steps behind, so each move beats exactly half
+/// of the others. With the moves in the order Rock, Paper, Scissors, Spock,
+/// Lizard this reproduces both the classic game and its five move variant.
+///
+/// The game also carries the rules a strategy guide is scored under: the
+/// symbols each player's moves and the desired outcomes are written with,
+/// and the points awarded per shape and per outcome.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Game {
+    moves: Vec<String>,
+    elf_symbols: Vec<String>,
+    you_symbols: Vec<String>,
+    /// Indexed by [`Outcome::index`].
+    outcome_symbols: [String; 3],
+    shape_scores: Vec<i64>,
+    /// Indexed by [`Outcome::index`].
+    outcome_scores: [i64; 3],
+}
+
+impl Game {
+    fn new(moves: &[&str]) -> Result<Self, GameError> {
+        Self::with_rules(Rules {
+            moves: moves.iter().map(|m| m.to_string()).collect(),
+            ..Rules::default()
+        })
     }
 
-    fn score(&self) -> u32 {
-        match self {
-            Self::Rock => 1,
-            Self::Paper => 2,
-            Self::Scissors => 3,
+    fn with_rules(rules: Rules) -> Result<Self, GameError> {
+        let Rules { moves, symbols, scores } = rules;
+        let n = moves.len();
+        match n {
+            n if n < 3 => return Err(GameError::TooFewMoves(n)),
+            n if n % 2 == 0 => return Err(GameError::EvenMoves(n)),
+            n if n > MAX_MOVES && (symbols.elf.is_none() || symbols.you.is_none()) => {
+                return Err(GameError::TooManyMoves(n))
+            }
+            _ => (),
+        }
+        if let Some(name) = first_duplicate(&moves) {
+            return Err(GameError::DuplicateMove(name.to_string()));
         }
+
+        let letters = |from: u8| (0..n).map(|i| ((from + i as u8) as char).to_string()).collect::<Vec<_>>();
+        let elf_symbols = symbols.elf.unwrap_or_else(|| letters(b'A'));
+        let you_symbols = symbols.you.unwrap_or_else(|| letters(b'Z' + 1 - n as u8));
+        let outcome_symbols = [
+            symbols.lose.unwrap_or_else(|| "X".into()),
+            symbols.draw.unwrap_or_else(|| "Y".into()),
+            symbols.win.unwrap_or_else(|| "Z".into()),
+        ];
+        let shape_scores = scores.shapes.unwrap_or_else(|| (1..=n as i64).collect());
+        let outcome_scores = [
+            scores.lose.unwrap_or(0),
+            scores.draw.unwrap_or(3),
+            scores.win.unwrap_or(6),
+        ];
+
+        for (player, symbols) in [("elf", &elf_symbols), ("you", &you_symbols)] {
+            if symbols.len() != n {
+                return Err(GameError::SymbolCount { player, expected: n, got: symbols.len() });
+            }
+        }
+        if shape_scores.len() != n {
+            return Err(GameError::ScoreCount { expected: n, got: shape_scores.len() });
+        }
+        for symbols in [&elf_symbols[..], &you_symbols[..], &outcome_symbols[..]] {
+            if let Some(symbol) = symbols.iter().find(|s| s.is_empty() || s.contains(char::is_whitespace)) {
+                return Err(GameError::InvalidSymbol(symbol.clone()));
+            }
+            if let Some(symbol) = first_duplicate(symbols) {
+                return Err(GameError::DuplicateSymbol(symbol.clone()));
+            }
+        }
+
+        Ok(Game {
+            moves,
+            elf_symbols,
+            you_symbols,
+            outcome_symbols,
+            shape_scores,
+            outcome_scores,
+        })
     }
 
-    fn outcome(&self, other: &Move) -> u32 {
-        match self {
-            Self::Rock => match other {
-                Self::Rock => 3,
-                Self::Paper => 0,
-                Self::Scissors => 6,
-            },
-            Self::Paper => match other {
-                Self::Rock => 6,
-                Self::Paper => 3,
-                Self::Scissors => 0,
-            },
-            Self::Scissors => match other {
-                Self::Rock => 0,
-                Self::Paper => 6,
-                Self::Scissors => 3,
-            },
+    fn rps() -> Self {
+        Self::new(&["Rock", "Paper", "Scissors"]).unwrap()
+    }
+
+    fn rpsls() -> Self {
+        Self::new(&["Rock", "Paper", "Scissors", "Spock", "Lizard"]).unwrap()
+    }
+
+    fn len(&self) -> usize {
+        self.moves.len()
+    }
+
+    fn elf_move(&self, symbol: &str) -> Result<PlayerMove, ()> {
+        let i = self.elf_symbols.iter().position(|s| s == symbol).ok_or(())?;
+        Ok(PlayerMove::Elf(Move(i)))
+    }
+
+    fn your_move(&self, symbol: &str) -> Result<PlayerMove, ()> {
+        let i = self.you_symbols.iter().position(|s| s == symbol).ok_or(())?;
+        Ok(PlayerMove::You(Move(i)))
+    }
+
+    fn parse_outcome(&self, symbol: &str) -> Result<Outcome, ()> {
+        let i = self.outcome_symbols.iter().position(|s| s == symbol).ok_or(())?;
+        Ok(OUTCOMES[i])
+    }
+
+    fn shape_score(&self, m: Move) -> i64 {
+        self.shape_scores[m.0]
+    }
+
+    fn outcome_score(&self, outcome: Outcome) -> i64 {
+        self.outcome_scores[outcome.index()]
+    }
+
+    /// How `you` fares against `other`.
+    fn outcome(&self, you: Move, other: Move) -> Outcome {
+        let steps = (you.0 + self.len() - other.0) % self.len();
+        if steps == 0 {
+            Outcome::Draw
+        } else if steps % 2 == 1 {
+            Outcome::Win
+        } else {
+            Outcome::Lose
         }
     }
+
+    /// The move that achieves `outcome` against `elf_move`. When several moves
+    /// do, the one with the best shape score is played.
+    fn given(&self, elf_move: Move, outcome: Outcome) -> Move {
+        (0..self.len())
+            .map(Move)
+            .filter(|&m| self.outcome(m, elf_move) == outcome)
+            .max_by_key(|&m| self.shape_score(m))
+            .unwrap()
+    }
+}
+
+fn first_duplicate(items: &[String]) -> Option<&String> {
+    items.iter().enumerate().find(|(i, s)| items[..*i].contains(s)).map(|(_, s)| s)
 }
 
 impl PlayerMove {
-    fn outcome(&self, other: &PlayerMove) -> u32 {
+    fn outcome(&self, other: &PlayerMove, game: &Game) -> i64 {
         match (self, other) {
-            (Self::Elf(em), Self::You(ym)) => ym.score() + ym.outcome(em),
-            (Self::You(ym), Self::Elf(em)) => ym.score() + ym.outcome(em),
+            (Self::Elf(em), Self::You(ym)) | (Self::You(ym), Self::Elf(em)) => {
+                game.shape_score(*ym) + game.outcome_score(game.outcome(*ym, *em))
+            }
             _ => panic!("Can't have a round with an Elf and an Elf or a You and a You"),
         }
     }
 
-    fn given(&self, outcome: &Outcome) -> Self {
+    fn given(&self, outcome: &Outcome, game: &Game) -> Self {
         match (self, outcome) {
-            (Self::Elf(em), o) => Self::You(Move::given(em, o)),
+            (Self::Elf(em), o) => Self::You(game.given(*em, *o)),
             _ => panic!("Can't have calculate a game given a You"),
         }
     }
 }
 
-fn part1(input: &str) -> String {
-    let score = input
-        .lines()
-        .map(|l| {
-            l.split(' ')
-                .map(|m| m.parse::<PlayerMove>().expect("Error parsing player move"))
-                .collect::<Vec<_>>()
-        })
-        .map(|round| round[0].outcome(&round[1]))
-        .sum::<u32>();
-
-    score.to_string()
-}
-
-fn part2(input: &str) -> String {
-    let score = input
-        .lines()
-        .map(|l| {
-            let instructions = l.split(' ').collect::<Vec<_>>();
-            let elf_move = instructions[0]
-                .parse::<PlayerMove>()
-                .expect("Error parsing elf move");
-            let outcome = instructions[1]
-                .parse::<Outcome>()
-                .expect("Error parsing outcome");
-            let your_move = elf_move.given(&outcome);
-            vec![elf_move, your_move]
-        })
-        .map(|round| round[0].outcome(&round[1]))
-        .sum::<u32>();
+/// The elf's move and yours for every round of a guide read as moves.
+fn read_moves<'a>(game: &'a Game, input: &'a str) -> impl Iterator<Item = Vec<PlayerMove>> + 'a {
+    input.lines().map(|l| {
+        let instructions = l.split(' ').collect::<Vec<_>>();
+        let elf_move = game
+            .elf_move(instructions[0])
+            .expect("Error parsing elf move");
+        let your_move = game
+            .your_move(instructions[1])
+            .expect("Error parsing your move");
+        vec![elf_move, your_move]
+    })
+}
+
+/// The elf's move and yours for every round of a guide read as outcomes.
+fn read_outcomes<'a>(game: &'a Game, input: &'a str) -> impl Iterator<Item = Vec<PlayerMove>> + 'a {
+    input.lines().map(|l| {
+        let instructions = l.split(' ').collect::<Vec<_>>();
+        let elf_move = game
+            .elf_move(instructions[0])
+            .expect("Error parsing elf move");
+        let outcome = game
+            .parse_outcome(instructions[1])
+            .expect("Error parsing outcome");
+        let your_move = elf_move.given(&outcome, game);
+        vec![elf_move, your_move]
+    })
+}
+
+/// Total score of a guide read as the elf's move and your move.
+fn play_moves(game: &Game, input: &str) -> i64 {
+    read_moves(game, input)
+        .map(|round| round[0].outcome(&round[1], game))
+        .sum::<i64>()
+}
 
-    score.to_string()
+/// Total score of a guide read as the elf's move and the outcome to play for.
+fn play_outcomes(game: &Game, input: &str) -> i64 {
+    read_outcomes(game, input)
+        .map(|round| round[0].outcome(&round[1], game))
+        .sum::<i64>()
+}
+
+fn part1(game: &Game, input: &str) -> String {
+    play_moves(game, input).to_string()
+}
+
+fn part2(game: &Game, input: &str) -> String {
+    play_outcomes(game, input).to_string()
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    const INPUT: &str = "A Y
+    pub(crate) const INPUT: &str = "A Y
 B X
 C Z";
 
     #[test]
     fn test_part1() {
-        assert_eq!("15", part1(INPUT));
+        assert_eq!("15", part1(&Game::rps(), INPUT));
     }
 
     #[test]
     fn test_part2() {
-        assert_eq!("12", part2(INPUT));
+        assert_eq!("12", part2(&Game::rps(), INPUT));
+    }
+
+    #[test]
+    fn test_rpsls_rules() {
+        let game = Game::rpsls();
+        let beats = |a: &str, b: &str| {
+            let index = |name: &str| Move(game.moves.iter().position(|m| m == name).unwrap());
+            game.outcome(index(a), index(b)) == Outcome::Win
+        };
+        for (winner, loser) in [
+            ("Scissors", "Paper"),
+            ("Paper", "Rock"),
+            ("Rock", "Lizard"),
+            ("Lizard", "Spock"),
+            ("Spock", "Scissors"),
+            ("Scissors", "Lizard"),
+            ("Lizard", "Paper"),
+            ("Paper", "Spock"),
+            ("Spock", "Rock"),
+            ("Rock", "Scissors"),
+        ] {
+            assert!(beats(winner, loser), "{} should beat {}", winner, loser);
+            assert!(!beats(loser, winner), "{} should lose to {}", loser, winner);
+        }
+    }
+
+    #[test]
+    fn test_every_move_beats_half_the_others() {
+        for n in [3, 5, 7, 9] {
+            let names = (0..n).map(|i| i.to_string()).collect::<Vec<_>>();
+            let game = Game::new(&names.iter().map(String::as_str).collect::<Vec<_>>()).unwrap();
+            for a in (0..n).map(Move) {
+                let wins = (0..n).map(Move).filter(|&b| game.outcome(a, b) == Outcome::Win).count();
+                assert_eq!((n - 1) / 2, wins);
+                for b in (0..n).map(Move) {
+                    assert_eq!(game.outcome(a, b) == Outcome::Win, game.outcome(b, a) == Outcome::Lose);
+                }
+                for outcome in [Outcome::Win, Outcome::Lose, Outcome::Draw] {
+                    assert_eq!(outcome, game.outcome(game.given(a, outcome), a));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_symbols() {
+        let game = Game::rpsls();
+        assert_eq!(Ok(PlayerMove::Elf(Move(4))), game.elf_move("E"));
+        assert_eq!(Ok(PlayerMove::You(Move(0))), game.your_move("V"));
+        assert_eq!(Ok(PlayerMove::You(Move(4))), game.your_move("Z"));
+        assert_eq!(Err(()), game.elf_move("F"));
+        assert_eq!(Err(()), game.elf_move("AB"));
+        assert_eq!(Err(()), game.your_move("A"));
+
+        // Rock (V) against Spock (D) loses, Lizard (Z) against Paper (B) wins.
+        assert_eq!("12", part1(&game, "D V\nB Z"));
+    }
+
+    #[test]
+    fn test_invalid_games() {
+        assert_eq!(Err(GameError::TooFewMoves(1)), Game::new(&["Rock"]));
+        assert_eq!(Err(GameError::EvenMoves(4)), Game::new(&["a", "b", "c", "d"]));
+        assert_eq!(Err(GameError::DuplicateMove("a".into())), Game::new(&["a", "b", "a"]));
+        assert_eq!(Err(GameError::TooManyMoves(15)), Game::new(&["x"; 15]));
+    }
+
+    #[test]
+    fn test_rules_file() {
+        let toml = r#"
+            moves = ["Rock", "Paper", "Scissors"]
+
+            [symbols]
+            elf = ["R", "P", "S"]
+            you = ["r", "p", "s"]
+            lose = "L"
+            draw = "D"
+            win = "W"
+
+            [scores]
+            shapes = [10, 20, 30]
+            lose = -5
+            win = 100
+        "#;
+        let game = Game::with_rules(Rules::from_toml(toml).unwrap()).unwrap();
+        // Paper beats Rock, Rock loses to Paper, Scissors draws Scissors.
+        assert_eq!((20 + 100 + 10 - 5 + 30 + 3).to_string(), part1(&game, "R p\nP r\nS s"));
+        assert_eq!((20 + 100 + 10 - 5 + 30 + 3).to_string(), part2(&game, "R W\nP L\nS D"));
+
+        let json = r#"{"moves": ["Rock", "Paper", "Scissors"], "scores": {"shapes": [3, 2, 1]}}"#;
+        let game = Game::with_rules(Rules::from_json(json).unwrap()).unwrap();
+        assert_eq!(Game { shape_scores: vec![3, 2, 1], ..Game::rps() }, game);
+        // Paper wins for 2 + 6, Rock loses for 3, Scissors draws for 1 + 3.
+        assert_eq!("15", part1(&game, INPUT));
+    }
+
+    #[test]
+    fn test_invalid_rules() {
+        let rules = |toml: &str| Rules::from_toml(toml).and_then(Game::with_rules);
+
+        assert!(matches!(rules("moves = 3"), Err(GameError::InvalidRules(_))));
+        assert!(matches!(rules("moves = [\"a\"]\ncolour = 1"), Err(GameError::InvalidRules(_))));
+        assert_eq!(
+            Err(GameError::SymbolCount { player: "elf", expected: 3, got: 2 }),
+            rules("moves = [\"a\", \"b\", \"c\"]\nsymbols.elf = [\"A\", \"B\"]")
+        );
+        assert_eq!(
+            Err(GameError::ScoreCount { expected: 3, got: 1 }),
+            rules("moves = [\"a\", \"b\", \"c\"]\nscores.shapes = [1]")
+        );
+        assert_eq!(
+            Err(GameError::DuplicateSymbol("X".into())),
+            rules("moves = [\"a\", \"b\", \"c\"]\nsymbols.win = \"X\"")
+        );
+        assert_eq!(
+            Err(GameError::InvalidSymbol("A A".into())),
+            rules("moves = [\"a\", \"b\", \"c\"]\nsymbols.elf = [\"A A\", \"B\", \"C\"]")
+        );
     }
 }
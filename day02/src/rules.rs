@@ -0,0 +1,61 @@
+use serde::Deserialize;
+
+use crate::GameError;
+
+/// A rules file as written by hand. Only `moves` is required; symbols and
+/// scores left out fall back to the puzzle's conventions:
+///
+/// ```toml
+/// moves = ["Rock", "Paper", "Scissors"]
+///
+/// [symbols]
+/// elf = ["A", "B", "C"]
+/// you = ["X", "Y", "Z"]
+/// lose = "X"
+/// draw = "Y"
+/// win = "Z"
+///
+/// [scores]
+/// shapes = [1, 2, 3]
+/// lose = 0
+/// draw = 3
+/// win = 6
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Rules {
+    pub moves: Vec<String>,
+    #[serde(default)]
+    pub symbols: Symbols,
+    #[serde(default)]
+    pub scores: Scores,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Symbols {
+    pub elf: Option<Vec<String>>,
+    pub you: Option<Vec<String>>,
+    pub lose: Option<String>,
+    pub draw: Option<String>,
+    pub win: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Scores {
+    pub shapes: Option<Vec<i64>>,
+    pub lose: Option<i64>,
+    pub draw: Option<i64>,
+    pub win: Option<i64>,
+}
+
+impl Rules {
+    pub fn from_toml(input: &str) -> Result<Self, GameError> {
+        toml::from_str(input).map_err(|e| GameError::InvalidRules(e.to_string()))
+    }
+
+    pub fn from_json(input: &str) -> Result<Self, GameError> {
+        serde_json::from_str(input).map_err(|e| GameError::InvalidRules(e.to_string()))
+    }
+}
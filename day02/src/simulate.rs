@@ -0,0 +1,299 @@
+use std::fmt;
+
+use crate::equilibrium::{self, payoff};
+use crate::{Game, Move, PlayerMove, OUTCOMES};
+
+/// 95% two-sided normal quantile.
+const Z_95: f64 = 1.96;
+
+/// SplitMix64: tiny, fast and fully determined by its seed, so a simulation
+/// can be replayed exactly.
+#[derive(Debug, Clone)]
+pub struct Rng(u64);
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Rng(seed)
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// Uniform in `[0, 1)`.
+    pub fn next_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    /// Index drawn with probability proportional to `weights`.
+    pub fn pick(&mut self, weights: &[f64]) -> usize {
+        let mut r = self.next_f64() * weights.iter().sum::<f64>();
+        for (i, &w) in weights.iter().enumerate() {
+            if r < w {
+                return i;
+            }
+            r -= w;
+        }
+        weights.iter().rposition(|&w| w > 0.0).unwrap_or(0)
+    }
+}
+
+/// How you choose your move once the elf's move is known.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Strategy {
+    /// For each elf move, a distribution over your moves.
+    Respond(Vec<Vec<f64>>),
+    /// For each elf move, a distribution over [`OUTCOMES`] to play for.
+    Aim(Vec<Vec<f64>>),
+    /// A distribution over your moves, ignoring the elf.
+    Mixed(Vec<f64>),
+    Fixed(Move),
+}
+
+impl Strategy {
+    fn play(&self, game: &Game, elf: Move, rng: &mut Rng) -> i64 {
+        let elf_move = PlayerMove::Elf(elf);
+        let your_move = match self {
+            Self::Respond(d) => PlayerMove::You(Move(rng.pick(&d[elf.0]))),
+            Self::Aim(d) => elf_move.given(&OUTCOMES[rng.pick(&d[elf.0])], game),
+            Self::Mixed(d) => PlayerMove::You(Move(rng.pick(d))),
+            Self::Fixed(m) => PlayerMove::You(*m),
+        };
+        elf_move.outcome(&your_move, game)
+    }
+}
+
+/// Score distribution of one strategy over all simulated tournaments.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Summary {
+    pub name: String,
+    pub mean: f64,
+    pub std_dev: f64,
+    /// 95% confidence interval of the mean tournament score.
+    pub ci: (f64, f64),
+    pub min: i64,
+    pub max: i64,
+}
+
+impl Summary {
+    fn new(name: String, scores: &[i64]) -> Self {
+        let n = scores.len() as f64;
+        let mean = scores.iter().sum::<i64>() as f64 / n;
+        let variance = if scores.len() > 1 {
+            scores.iter().map(|&s| (s as f64 - mean).powi(2)).sum::<f64>() / (n - 1.0)
+        } else {
+            0.0
+        };
+        let std_dev = variance.sqrt();
+        let margin = Z_95 * std_dev / n.sqrt();
+
+        Summary {
+            name,
+            mean,
+            std_dev,
+            ci: (mean - margin, mean + margin),
+            min: *scores.iter().min().unwrap(),
+            max: *scores.iter().max().unwrap(),
+        }
+    }
+}
+
+/// For each elf move, how often each column value follows it in the guide.
+/// Elf moves the guide never shows get a uniform row; the simulated elf never
+/// plays them anyway.
+fn conditional(game: &Game, pairs: &[(Move, usize)], columns: usize) -> Vec<Vec<f64>> {
+    let mut counts = vec![vec![0.0; columns]; game.len()];
+    for &(elf, column) in pairs {
+        counts[elf.0][column] += 1.0;
+    }
+    for row in counts.iter_mut() {
+        if row.iter().all(|&c| c == 0.0) {
+            row.fill(1.0);
+        }
+    }
+    counts
+}
+
+/// The strategies worth comparing for a guide: the guide itself under each
+/// reading its symbols allow, then the guide-free alternatives.
+pub fn strategies(game: &Game, input: &str) -> Vec<(String, Strategy)> {
+    let (opponent, _) = equilibrium::opponent(game, input);
+    let rounds = input
+        .lines()
+        .map(|l| {
+            let mut symbols = l.split(' ');
+            let elf = match game.elf_move(symbols.next().unwrap_or("")) {
+                Ok(PlayerMove::Elf(m)) => m,
+                _ => panic!("Error parsing elf move"),
+            };
+            (elf, symbols.next().unwrap_or(""))
+        })
+        .collect::<Vec<_>>();
+
+    let mut strategies = Vec::new();
+    let moves = rounds
+        .iter()
+        .map(|&(elf, s)| match game.your_move(s) {
+            Ok(PlayerMove::You(m)) => Some((elf, m.0)),
+            _ => None,
+        })
+        .collect::<Option<Vec<_>>>();
+    if let Some(moves) = moves {
+        let d = conditional(game, &moves, game.len());
+        strategies.push(("guide as moves".to_string(), Strategy::Respond(d)));
+    }
+    let outcomes = rounds
+        .iter()
+        .map(|&(elf, s)| game.parse_outcome(s).ok().map(|o| (elf, o.index())))
+        .collect::<Option<Vec<_>>>();
+    if let Some(outcomes) = outcomes {
+        let d = conditional(game, &outcomes, OUTCOMES.len());
+        strategies.push(("guide as outcomes".to_string(), Strategy::Aim(d)));
+    }
+
+    let payoff = payoff(game);
+    let (optimal, _) = equilibrium::solve(&payoff);
+    strategies.push(("optimal mix".to_string(), Strategy::Mixed(optimal)));
+    strategies.push(("uniform".to_string(), Strategy::Mixed(vec![1.0; game.len()])));
+
+    let (best, _) = equilibrium::best_response(&payoff, &opponent);
+    strategies.push((format!("best response ({})", game.moves[best.0]), Strategy::Fixed(best)));
+    for (i, name) in game.moves.iter().enumerate() {
+        strategies.push((format!("always {}", name), Strategy::Fixed(Move(i))));
+    }
+    strategies
+}
+
+/// Plays `tournaments` tournaments of `rounds` rounds for every strategy,
+/// against an elf sampling its moves from `opponent`. Every strategy faces
+/// the same sequence of elf moves so their differences are not sampling noise
+/// in the opponent.
+pub fn simulate(
+    game: &Game,
+    opponent: &[f64],
+    strategies: &[(String, Strategy)],
+    tournaments: usize,
+    rounds: usize,
+    seed: u64,
+) -> Vec<Summary> {
+    strategies
+        .iter()
+        .map(|(name, strategy)| {
+            let mut elf_rng = Rng::new(seed);
+            let mut your_rng = Rng::new(!seed);
+            let scores = (0..tournaments.max(1))
+                .map(|_| {
+                    (0..rounds)
+                        .map(|_| {
+                            let elf = Move(elf_rng.pick(opponent));
+                            strategy.play(game, elf, &mut your_rng)
+                        })
+                        .sum::<i64>()
+                })
+                .collect::<Vec<_>>();
+            Summary::new(name.clone(), &scores)
+        })
+        .collect()
+}
+
+pub struct Report<'a>(pub &'a [Summary]);
+
+impl fmt::Display for Report<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let width = self.0.iter().map(|s| s.name.len()).max().unwrap_or(0);
+        writeln!(
+            f,
+            "{:<w$} {:>10} {:>9} {:>23} {:>8} {:>8}",
+            "strategy", "mean", "std dev", "95% ci", "min", "max",
+            w = width
+        )?;
+        for s in self.0 {
+            writeln!(
+                f,
+                "{:<w$} {:>10.1} {:>9.1} {:>23} {:>8} {:>8}",
+                s.name,
+                s.mean,
+                s.std_dev,
+                format!("[{:.1}, {:.1}]", s.ci.0, s.ci.1),
+                s.min,
+                s.max,
+                w = width
+            )?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::INPUT;
+
+    #[test]
+    fn test_rng_is_reproducible() {
+        let draws = |seed| {
+            let mut rng = Rng::new(seed);
+            (0..5).map(|_| rng.next_u64()).collect::<Vec<_>>()
+        };
+        assert_eq!(draws(7), draws(7));
+        assert_ne!(draws(7), draws(8));
+
+        let mut rng = Rng::new(1);
+        let mut counts = [0; 3];
+        for _ in 0..30000 {
+            counts[rng.pick(&[1.0, 0.0, 2.0])] += 1;
+        }
+        assert_eq!(0, counts[1]);
+        assert!((counts[2] as f64 / counts[0] as f64 - 2.0).abs() < 0.1);
+    }
+
+    #[test]
+    fn test_strategies() {
+        let game = Game::rps();
+        let contenders = strategies(&game, INPUT);
+        let names = contenders.iter().map(|(n, _)| n.as_str()).collect::<Vec<_>>();
+        assert_eq!(
+            vec![
+                "guide as moves",
+                "guide as outcomes",
+                "optimal mix",
+                "uniform",
+                "best response (Scissors)",
+                "always Rock",
+                "always Paper",
+                "always Scissors"
+            ],
+            names
+        );
+
+        // A guide using the elf's move letters can only be read as moves.
+        let game = Game::rpsls();
+        assert_eq!("guide as moves", strategies(&game, "A V\nB W")[0].0);
+        assert_eq!("optimal mix", strategies(&game, "A V\nB W")[1].0);
+    }
+
+    #[test]
+    fn test_simulation() {
+        let game = Game::rps();
+        let guide = "A Y\nA Y";
+        let (opponent, rounds) = equilibrium::opponent(&game, guide);
+        let contenders = strategies(&game, guide);
+        let summaries = simulate(&game, &opponent, &contenders, 200, rounds, 42);
+
+        // The elf only ever plays Rock, so both readings of the guide are
+        // deterministic: Paper for 8 and a Rock draw for 4 per round.
+        assert_eq!(Summary::new("guide as moves".into(), &[16]), summaries[0]);
+        assert_eq!(16.0, summaries[0].mean);
+        assert_eq!((8, 8), (summaries[1].min, summaries[1].max));
+
+        // Uniform play against Rock averages (4 + 8 + 3) / 3 per round.
+        let uniform = &summaries[3];
+        assert!((uniform.mean - 10.0).abs() < 0.5, "{:?}", uniform);
+        assert!(uniform.ci.0 < uniform.mean && uniform.mean < uniform.ci.1);
+        assert_eq!(summaries, simulate(&game, &opponent, &contenders, 200, rounds, 42));
+    }
+}
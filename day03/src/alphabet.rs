@@ -0,0 +1,213 @@
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt;
+
+use serde::Deserialize;
+
+/// Item sets are `u128` bitsets, one bit per item type.
+pub const MAX_ITEMS: usize = u128::BITS as usize;
+
+/// An alphabet file. Priorities default to 1, 2, 3, ... in the order of
+/// `items`:
+///
+/// ```toml
+/// items = "0123456789αβγ"
+/// priorities = [10, 1, 2, 3, 4, 5, 6, 7, 8, 9, 100, 200, 300]
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Config {
+    pub items: String,
+    pub priorities: Option<Vec<u32>>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AlphabetError {
+    Empty,
+    TooManyItems(usize),
+    DuplicateItem(char),
+    PriorityCount { expected: usize, got: usize },
+    InvalidConfig(String),
+}
+
+impl fmt::Display for AlphabetError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Empty => write!(f, "an alphabet needs at least one item type"),
+            Self::TooManyItems(n) => write!(f, "{} item types, at most {} are supported", n, MAX_ITEMS),
+            Self::DuplicateItem(c) => write!(f, "item type {:?} appears twice", c),
+            Self::PriorityCount { expected, got } => {
+                write!(f, "expected {} priorities, one per item type, got {}", expected, got)
+            }
+            Self::InvalidConfig(e) => write!(f, "invalid alphabet file: {}", e),
+        }
+    }
+}
+
+impl Error for AlphabetError {}
+
+/// The item types rucksacks can hold and what each is worth.
+///
+/// Item types are kept in order of priority, ties in the order they were
+/// given, and an item's position is its bit in an item set. The lowest set
+/// bit of a set is therefore its lowest priority item.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Alphabet {
+    items: Vec<char>,
+    priorities: Vec<u32>,
+    /// Bit plus one of each ASCII item type, 0 for characters that are not
+    /// items, so the common case avoids hashing.
+    ascii: [u8; 128],
+    other: HashMap<char, u8>,
+}
+
+impl Alphabet {
+    pub fn new(items: &[(char, u32)]) -> Result<Self, AlphabetError> {
+        if items.is_empty() {
+            return Err(AlphabetError::Empty);
+        }
+        if items.len() > MAX_ITEMS {
+            return Err(AlphabetError::TooManyItems(items.len()));
+        }
+        let mut sorted = items.to_vec();
+        sorted.sort_by_key(|&(_, p)| p);
+
+        let mut ascii = [0; 128];
+        let mut other = HashMap::new();
+        for (bit, &(c, _)) in sorted.iter().enumerate() {
+            let seen = if c.is_ascii() {
+                std::mem::replace(&mut ascii[c as usize], bit as u8 + 1) != 0
+            } else {
+                other.insert(c, bit as u8).is_some()
+            };
+            if seen {
+                return Err(AlphabetError::DuplicateItem(c));
+            }
+        }
+
+        Ok(Alphabet {
+            items: sorted.iter().map(|&(c, _)| c).collect(),
+            priorities: sorted.iter().map(|&(_, p)| p).collect(),
+            ascii,
+            other,
+        })
+    }
+
+    /// The puzzle's alphabet: `a` through `z` are 1 to 26, `A` through `Z`
+    /// are 27 to 52.
+    pub fn letters() -> Self {
+        let items = ('a'..='z').chain('A'..='Z').zip(1..).collect::<Vec<_>>();
+        Self::new(&items).expect("the letters are a valid alphabet")
+    }
+
+    pub fn with_config(config: Config) -> Result<Self, AlphabetError> {
+        let items = config.items.chars().collect::<Vec<_>>();
+        let priorities = config.priorities.unwrap_or_else(|| (1..=items.len() as u32).collect());
+        if priorities.len() != items.len() {
+            return Err(AlphabetError::PriorityCount { expected: items.len(), got: priorities.len() });
+        }
+        Self::new(&items.into_iter().zip(priorities).collect::<Vec<_>>())
+    }
+
+    pub fn from_toml(input: &str) -> Result<Self, AlphabetError> {
+        toml::from_str(input)
+            .map_err(|e| AlphabetError::InvalidConfig(e.to_string()))
+            .and_then(Self::with_config)
+    }
+
+    pub fn from_json(input: &str) -> Result<Self, AlphabetError> {
+        serde_json::from_str(input)
+            .map_err(|e| AlphabetError::InvalidConfig(e.to_string()))
+            .and_then(Self::with_config)
+    }
+
+    /// The bit of item type `item`, if it is one.
+    pub fn bit(&self, item: char) -> Option<u32> {
+        if item.is_ascii() {
+            self.ascii[item as usize].checked_sub(1).map(u32::from)
+        } else {
+            self.other.get(&item).map(|&b| u32::from(b))
+        }
+    }
+
+    /// The item types in `items` as a bitset. Intersecting sets is then a
+    /// bitwise and. Characters outside the alphabet are ignored.
+    pub fn set(&self, items: &str) -> u128 {
+        items.chars().filter_map(|c| self.bit(c)).fold(0, |set, b| set | 1 << b)
+    }
+
+    /// Priority of the lowest priority item type in `set`, or 0 for an empty set.
+    pub fn lowest_priority(&self, set: u128) -> u32 {
+        if set == 0 {
+            0
+        } else {
+            self.priorities[set.trailing_zeros() as usize]
+        }
+    }
+
+    /// The item types in `set`, lowest priority first.
+    pub fn items(&self, set: u128) -> Vec<char> {
+        (0..self.items.len()).filter(|&b| set & 1 << b != 0).map(|b| self.items[b]).collect()
+    }
+
+    /// Every item type in `set` with its priority, lowest priority first.
+    pub fn describe(&self, set: u128) -> String {
+        if set == 0 {
+            return "none".to_string();
+        }
+        (0..self.items.len())
+            .filter(|&b| set & 1 << b != 0)
+            .map(|b| format!("{} ({})", self.items[b], self.priorities[b]))
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_letters() {
+        let letters = Alphabet::letters();
+        assert_eq!(1, letters.lowest_priority(letters.set("a")));
+        assert_eq!(26, letters.lowest_priority(letters.set("z")));
+        assert_eq!(27, letters.lowest_priority(letters.set("A")));
+        assert_eq!(52, letters.lowest_priority(letters.set("Z")));
+        assert_eq!(None, letters.bit('1'));
+        assert_eq!(None, letters.bit('é'));
+
+        assert_eq!(1 | 1 << 26, letters.set("aAa"));
+        assert_eq!(16, letters.lowest_priority(letters.set("pLp") & letters.set("p")));
+        assert_eq!(0, letters.lowest_priority(letters.set("abc") & letters.set("xyz")));
+        assert_eq!("r (18), Z (52)", letters.describe(letters.set("Zr")));
+        assert_eq!(vec!['r', 'Z'], letters.items(letters.set("ZrZ")));
+    }
+
+    #[test]
+    fn test_config() {
+        let alphabet = Alphabet::from_toml("items = \"0123456789αβγ\"\npriorities = [10, 1, 2, 3, 4, 5, 6, 7, 8, 9, 100, 200, 300]").unwrap();
+        assert_eq!(10, alphabet.lowest_priority(alphabet.set("0")));
+        assert_eq!(200, alphabet.lowest_priority(alphabet.set("β")));
+        // Bits follow priority, so 1 sorts before 0.
+        assert_eq!(1, alphabet.lowest_priority(alphabet.set("10")));
+        assert_eq!("0 (10), γ (300)", alphabet.describe(alphabet.set("γ0")));
+
+        let json = Alphabet::from_json(r#"{"items": "xyz"}"#).unwrap();
+        assert_eq!(3, json.lowest_priority(json.set("z")));
+    }
+
+    #[test]
+    fn test_invalid_alphabets() {
+        assert_eq!(Err(AlphabetError::Empty), Alphabet::new(&[]));
+        assert_eq!(Err(AlphabetError::DuplicateItem('a')), Alphabet::new(&[('a', 1), ('b', 2), ('a', 3)]));
+        assert_eq!(Err(AlphabetError::DuplicateItem('é')), Alphabet::new(&[('é', 1), ('é', 2)]));
+        let many = (0..200).map(|i| (char::from_u32(0x100 + i).unwrap(), i)).collect::<Vec<_>>();
+        assert_eq!(Err(AlphabetError::TooManyItems(200)), Alphabet::new(&many));
+        assert_eq!(
+            Err(AlphabetError::PriorityCount { expected: 2, got: 1 }),
+            Alphabet::from_toml("items = \"ab\"\npriorities = [1]")
+        );
+        assert!(matches!(Alphabet::from_toml("items = \"ab\"\nweights = [1]"), Err(AlphabetError::InvalidConfig(_))));
+    }
+}
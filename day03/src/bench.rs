@@ -0,0 +1,148 @@
+use std::collections::HashSet;
+use std::fmt;
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+use crate::alphabet::Alphabet;
+
+/// The original solution, collecting every compartment and rucksack into a
+/// `HashSet<char>`. Kept to measure the bitset version against.
+pub mod hashset {
+    use super::HashSet;
+    use crate::compartments;
+
+    /// Priority of the first shared item, or 0 when there is none or it is
+    /// not a letter, as the bitset version scores it.
+    fn priority(shared: &str) -> u32 {
+        match shared.chars().next() {
+            Some(c @ 'A'..='Z') => c as u32 - 38,
+            Some(c @ 'a'..='z') => c as u32 - 96,
+            _ => 0,
+        }
+    }
+
+    pub fn part1(input: &str) -> String {
+        let sum = input
+            .lines()
+            .map(|line| {
+                let (a, b) = compartments(line);
+                (a.chars().collect::<HashSet<_>>(), b.chars().collect::<HashSet<_>>())
+            })
+            .map(|(a, b)| a.intersection(&b).collect::<String>())
+            .map(|s| priority(&s))
+            .sum::<u32>();
+
+        sum.to_string()
+    }
+
+    pub fn part2(input: &str) -> String {
+        let sum = input
+            .lines()
+            .map(|line| line.chars().collect::<HashSet<_>>())
+            .collect::<Vec<_>>()
+            .chunks_exact(3)
+            .map(|c| {
+                let ab = c[0].intersection(&c[1]).copied().collect();
+                c[2].intersection(&ab).collect::<String>()
+            })
+            .map(|s| priority(&s))
+            .sum::<u32>();
+
+        sum.to_string()
+    }
+}
+
+/// Time per run of both parts for each implementation.
+#[derive(Debug, Clone)]
+pub struct Report {
+    pub iterations: u32,
+    pub timings: Vec<(&'static str, Duration)>,
+    /// Parts the implementations answer differently, with the HashSet answer
+    /// first. Inputs with several shared items or items that are not letters
+    /// can do this, as the HashSet version picks whichever item it sees first.
+    pub mismatches: Vec<(&'static str, String, String)>,
+}
+
+fn time(iterations: u32, input: &str, part1: impl Fn(&str) -> String, part2: impl Fn(&str) -> String) -> Duration {
+    let start = Instant::now();
+    for _ in 0..iterations {
+        black_box(part1(black_box(input)));
+        black_box(part2(black_box(input)));
+    }
+    start.elapsed() / iterations
+}
+
+/// Runs both implementations `iterations` times over `input`, and compares
+/// their answers.
+pub fn run(input: &str, iterations: u32) -> Report {
+    let iterations = iterations.max(1);
+    let letters = Alphabet::letters();
+    let part1 = |input: &str| crate::part1(&letters, input);
+    let part2 = |input: &str| crate::part2(&letters, input, crate::GROUP_SIZE);
+    let mismatches = [("part1", hashset::part1(input), part1(input)), ("part2", hashset::part2(input), part2(input))]
+        .into_iter()
+        .filter(|(_, reference, bitset)| reference != bitset)
+        .collect();
+
+    Report {
+        mismatches,
+        iterations,
+        timings: vec![
+            ("HashSet<char>", time(iterations, input, hashset::part1, hashset::part2)),
+            ("bitset", time(iterations, input, part1, part2)),
+        ],
+    }
+}
+
+impl fmt::Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "{} iterations of part1 + part2", self.iterations)?;
+        let baseline = self.timings[0].1.as_secs_f64();
+        for (name, per_run) in &self.timings {
+            writeln!(
+                f,
+                "{:<14} {:>12?} per run  {:>6.1}x",
+                name,
+                per_run,
+                baseline / per_run.as_secs_f64().max(f64::MIN_POSITIVE)
+            )?;
+        }
+        for (part, reference, bitset) in &self.mismatches {
+            writeln!(f, "{} answers differ: {} with HashSet<char>, {} with bitset", part, reference, bitset)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::INPUT;
+
+    #[test]
+    fn test_implementations_agree() {
+        assert_eq!("157", hashset::part1(INPUT));
+        assert_eq!("70", hashset::part2(INPUT));
+
+        let report = run(INPUT, 3);
+        assert_eq!(3, report.iterations);
+        assert_eq!(vec!["HashSet<char>", "bitset"], report.timings.iter().map(|t| t.0).collect::<Vec<_>>());
+        assert!(report.mismatches.is_empty());
+    }
+
+    #[test]
+    fn test_invalid_input_is_reported() {
+        // Multi-byte items, a digit, and a group of one rucksack.
+        let input = "éaéb\n1b1c\nxyxz\nzz";
+        assert_eq!("50", hashset::part1(input));
+        assert_eq!("0", hashset::part2(input));
+        assert!(run(input, 1).mismatches.is_empty());
+
+        let report = Report {
+            iterations: 1,
+            timings: vec![("HashSet<char>", Duration::from_millis(2)), ("bitset", Duration::from_millis(1))],
+            mismatches: vec![("part1", "2".into(), "1".into())],
+        };
+        assert!(report.to_string().ends_with("part1 answers differ: 2 with HashSet<char>, 1 with bitset\n"));
+    }
+}
@@ -0,0 +1,169 @@
+use std::fmt;
+
+use crate::alphabet::Alphabet;
+
+/// Outcome of searching for a grouping of the rucksacks into triples that
+/// each share exactly one item type.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Search {
+    /// Each group as 0-based line indices, or `None` when no grouping exists.
+    pub groups: Option<Vec<[usize; 3]>>,
+    /// Candidate triples tried before the search finished.
+    pub nodes: u64,
+    /// The badge of every group, in the order of `groups`.
+    pub badges: Vec<char>,
+}
+
+/// Two more rucksacks completing a group, and the group's badge.
+type Triple = (usize, usize, u128);
+
+struct Solver {
+    sets: Vec<u128>,
+    used: Vec<bool>,
+    groups: Vec<[usize; 3]>,
+    badges: Vec<u128>,
+    nodes: u64,
+}
+
+impl Solver {
+    /// Chooses the free rucksack with the fewest candidate triples, so a dead
+    /// end shows up as early as possible.
+    fn pivot(&self) -> Option<(usize, Vec<Triple>)> {
+        let free = (0..self.sets.len()).filter(|&i| !self.used[i]).collect::<Vec<_>>();
+        let mut best: Option<(usize, Vec<Triple>)> = None;
+        for &i in &free {
+            let triples = self.triples(i, &free, best.as_ref().map_or(usize::MAX, |b| b.1.len()));
+            if best.as_ref().is_none_or(|b| triples.len() < b.1.len()) {
+                let done = triples.is_empty();
+                best = Some((i, triples));
+                if done {
+                    break;
+                }
+            }
+        }
+        best
+    }
+
+    /// Pairs of other free rucksacks sharing exactly one item type with `i`,
+    /// with that item. Stops once `limit` pairs are found, as the caller is
+    /// then not interested.
+    fn triples(&self, i: usize, free: &[usize], limit: usize) -> Vec<Triple> {
+        let mut triples = Vec::new();
+        for (a, &j) in free.iter().enumerate() {
+            let shared = self.sets[i] & self.sets[j];
+            // An empty pairwise intersection rules out every third rucksack.
+            if j == i || shared == 0 {
+                continue;
+            }
+            for &k in &free[a + 1..] {
+                let badge = shared & self.sets[k];
+                if k != i && badge.count_ones() == 1 {
+                    triples.push((j, k, badge));
+                    if triples.len() >= limit {
+                        return triples;
+                    }
+                }
+            }
+        }
+        triples
+    }
+
+    fn solve(&mut self) -> bool {
+        let Some((i, triples)) = self.pivot() else {
+            return true;
+        };
+        for (j, k, badge) in triples {
+            self.nodes += 1;
+            for r in [i, j, k] {
+                self.used[r] = true;
+            }
+            let mut group = [i, j, k];
+            group.sort_unstable();
+            self.groups.push(group);
+            self.badges.push(badge);
+            if self.solve() {
+                return true;
+            }
+            self.groups.pop();
+            self.badges.pop();
+            for r in [i, j, k] {
+                self.used[r] = false;
+            }
+        }
+        false
+    }
+}
+
+/// Searches for a partition of the rucksacks in `input`, in any order, into
+/// triples sharing exactly one item type. The search is exhaustive, so a
+/// `None` grouping proves no such partition exists.
+pub fn infer(alphabet: &Alphabet, input: &str) -> Search {
+    let sets = input.lines().map(|line| alphabet.set(line)).collect::<Vec<_>>();
+    let n = sets.len();
+    let mut solver = Solver { used: vec![false; n], sets, groups: Vec::new(), badges: Vec::new(), nodes: 0 };
+
+    let found = n.is_multiple_of(3) && solver.solve();
+    let (mut groups, mut badges) = (solver.groups, solver.badges);
+    if found {
+        let mut order = (0..groups.len()).collect::<Vec<_>>();
+        order.sort_by_key(|&g| groups[g]);
+        badges = order.iter().map(|&g| badges[g]).collect();
+        groups.sort_unstable();
+    }
+    let badges = badges.into_iter().flat_map(|b| alphabet.items(b)).collect();
+    Search { groups: found.then_some(groups), nodes: solver.nodes, badges }
+}
+
+impl fmt::Display for Search {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match &self.groups {
+            Some(groups) => {
+                for (group, badge) in groups.iter().zip(&self.badges) {
+                    let lines = group.map(|i| (i + 1).to_string()).join(", ");
+                    writeln!(f, "lines {}: {}", lines, badge)?;
+                }
+                writeln!(f, "{} groups found after trying {} triples", groups.len(), self.nodes)
+            }
+            None => writeln!(
+                f,
+                "no grouping into triples sharing exactly one item exists ({} triples tried)",
+                self.nodes
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::INPUT;
+
+    fn valid(input: &str, groups: &[[usize; 3]]) -> bool {
+        let sets = input.lines().map(|l| Alphabet::letters().set(l)).collect::<Vec<_>>();
+        let mut seen = groups.iter().flatten().copied().collect::<Vec<_>>();
+        seen.sort_unstable();
+        seen == (0..sets.len()).collect::<Vec<_>>()
+            && groups.iter().all(|g| (sets[g[0]] & sets[g[1]] & sets[g[2]]).count_ones() == 1)
+    }
+
+    #[test]
+    fn test_infer_shuffled_groups() {
+        let lines = INPUT.lines().collect::<Vec<_>>();
+        let shuffled = [lines[4], lines[0], lines[3], lines[2], lines[5], lines[1]].join("\n");
+
+        let search = infer(&Alphabet::letters(), &shuffled);
+        let groups = search.groups.clone().unwrap();
+        assert!(valid(&shuffled, &groups), "{:?}", groups);
+        assert_eq!(2, search.badges.len());
+    }
+
+    #[test]
+    fn test_no_grouping() {
+        assert_eq!(None, infer(&Alphabet::letters(), "ab\ncd\nef").groups);
+        // Every triple shares both a and b.
+        assert_eq!(None, infer(&Alphabet::letters(), "abab\nabab\nabab\nabab\nabab\nabab").groups);
+        // Not a multiple of three.
+        assert_eq!(None, infer(&Alphabet::letters(), "aa\naa").groups);
+        assert_eq!(Some(vec![]), infer(&Alphabet::letters(), "").groups);
+    }
+}
@@ -1,57 +1,116 @@
-use std::collections::HashSet;
 use std::{env, fs, io, path::Path};
 
+mod alphabet;
+mod bench;
+mod infer;
+mod validate;
+
+use alphabet::Alphabet;
+
 fn main() -> io::Result<()> {
     let mut dir = env::current_exe()?;
     dir.pop();
     let dir = dir.join(Path::new("../../day03/input.txt"));
 
-    let input = fs::read_to_string(dir)?;
-    println!("part1: {}", part1(&input));
-    println!("part2: {}", part2(&input));
+    let (flags, args): (Vec<String>, Vec<String>) = env::args().skip(1).partition(|a| a.starts_with("--"));
+    let group_size = match flags.iter().find_map(|f| f.strip_prefix("--group-size=")) {
+        Some(n) => n.parse().ok().filter(|&n| n > 0).ok_or_else(|| {
+            let usage = format!("invalid group size {:?}, expected a positive number", n);
+            io::Error::new(io::ErrorKind::InvalidInput, usage)
+        })?,
+        None => GROUP_SIZE,
+    };
+
+    let path = flags
+        .iter()
+        .find_map(|f| f.strip_prefix("--input="))
+        .map_or(dir, |p| p.into());
+    let alphabet = match flags.iter().find_map(|f| f.strip_prefix("--alphabet=")) {
+        Some(config) => {
+            let text = fs::read_to_string(config)?;
+            if config.ends_with(".json") {
+                Alphabet::from_json(&text)
+            } else {
+                Alphabet::from_toml(&text)
+            }
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?
+        }
+        None => Alphabet::letters(),
+    };
+    let input = fs::read_to_string(path)?;
+    match args.first().map(String::as_str) {
+        Some("bench") => {
+            let iterations = args.get(1).and_then(|i| i.parse().ok()).unwrap_or(1000);
+            print!("{}", bench::run(&input, iterations));
+        }
+        Some("shared") => {
+            for (i, shared) in groups(&alphabet, &input, group_size).enumerate() {
+                let first = i * group_size + 1;
+                println!(
+                    "group {} (lines {}-{}): {}",
+                    i + 1,
+                    first,
+                    first + group_size - 1,
+                    alphabet.describe(shared)
+                );
+            }
+        }
+        Some("infer") => print!("{}", infer::infer(&alphabet, &input)),
+        Some("validate") => {
+            let issues = validate::validate(&alphabet, &input, group_size);
+            for issue in &issues {
+                println!("{}", issue);
+            }
+            println!("{} issues in {} rucksacks", issues.len(), input.lines().count());
+        }
+        _ => {
+            println!("part1: {}", part1(&alphabet, &input));
+            println!("part2: {}", part2(&alphabet, &input, group_size));
+        }
+    }
     Ok(())
 }
 
-fn part1(input: &str) -> String {
+/// Number of elves in a group, unless `--group-size` says otherwise.
+const GROUP_SIZE: usize = 3;
+
+/// The two compartments of a rucksack, split halfway by items rather than
+/// bytes so multi-byte item types stay whole.
+fn compartments(line: &str) -> (&str, &str) {
+    let half = line.chars().count() / 2;
+    let mid = line.char_indices().nth(half).map_or(line.len(), |(i, _)| i);
+    line.split_at(mid)
+}
+
+fn part1(alphabet: &Alphabet, input: &str) -> String {
     let sum = input
         .lines()
         .map(|line| {
-            let a = line[0..(line.len() / 2)].chars().collect::<HashSet<_>>();
-            let b = line[(line.len() / 2)..].chars().collect::<HashSet<_>>();
-            (a, b)
-        })
-        .map(|(a, b)| a.intersection(&b).collect::<String>())
-        .map(|s| {
-            let c = s.as_bytes()[0] as u32;
-            match c {
-                65..=90 => c - 38,
-                97..=122 => c - 96,
-                _ => unimplemented!(),
-            }
+            let (a, b) = compartments(line);
+            alphabet.set(a) & alphabet.set(b)
         })
+        .map(|shared| alphabet.lowest_priority(shared))
         .sum::<u32>();
 
     sum.to_string()
 }
 
-fn part2(input: &str) -> String {
-    let sum = input
-        .lines()
-        .map(|line| line.chars().collect::<HashSet<_>>())
-        .collect::<Vec<_>>()
-        .chunks(3)
-        .map(|c| {
-            let ab = c[0].intersection(&c[1]).copied().collect();
-            c[2].intersection(&ab).collect::<String>()
-        })
-        .map(|s| {
-            let c = s.as_bytes()[0] as u32;
-            match c {
-                65..=90 => c - 38,
-                97..=122 => c - 96,
-                _ => unimplemented!(),
-            }
-        })
+/// The item types shared by every rucksack in each group of `size`
+/// consecutive lines. A trailing group with fewer rucksacks is ignored.
+fn groups<'a>(alphabet: &'a Alphabet, input: &'a str, size: usize) -> impl Iterator<Item = u128> + 'a {
+    let mut lines = input.lines();
+    std::iter::from_fn(move || {
+        let (count, shared) = lines
+            .by_ref()
+            .take(size)
+            .fold((0, u128::MAX), |(count, shared), line| (count + 1, shared & alphabet.set(line)));
+        (count == size && size > 0).then_some(shared)
+    })
+}
+
+fn part2(alphabet: &Alphabet, input: &str, group_size: usize) -> String {
+    let sum = groups(alphabet, input, group_size)
+        .map(|badges| alphabet.lowest_priority(badges))
         .sum::<u32>();
 
     sum.to_string()
@@ -61,7 +120,7 @@ fn part2(input: &str) -> String {
 mod tests {
     use super::*;
 
-    const INPUT: &str = "vJrwpWtwJgWrhcsFMMfFFhFp
+    pub(crate) const INPUT: &str = "vJrwpWtwJgWrhcsFMMfFFhFp
 jqHRNqRjqzjGDLGLrsFMfFZSrLrFZsSL
 PmmdzqPrVvPwwTWBwg
 wMqvLMZHhHMvwLHjbvcjnnSBnvTQFn
@@ -70,11 +129,31 @@ CrZsJsPPZsGzwwsLwLmpwMDw";
 
     #[test]
     fn test_part1() {
-        assert_eq!("157", part1(INPUT));
+        assert_eq!("157", part1(&Alphabet::letters(), INPUT));
     }
 
     #[test]
     fn test_part2() {
-        assert_eq!("70", part2(INPUT));
+        assert_eq!("70", part2(&Alphabet::letters(), INPUT, GROUP_SIZE));
+    }
+
+    #[test]
+    fn test_group_sizes() {
+        let letters = Alphabet::letters();
+        let pairs = vec![letters.set("FMfrs"), letters.set("BTqvw"), letters.set("GJZ")];
+        assert_eq!(pairs, groups(&letters, INPUT, 2).collect::<Vec<_>>());
+        assert_eq!(vec![0], groups(&letters, INPUT, 6).collect::<Vec<_>>());
+        // The last two rucksacks do not make up a group of four.
+        assert_eq!(1, groups(&letters, INPUT, 4).count());
+        assert_eq!(vec![letters.set("r"), letters.set("Z")], groups(&letters, INPUT, 3).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_custom_alphabet() {
+        let digits = Alphabet::from_toml("items = \"0123456789€\"\npriorities = [1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 100]").unwrap();
+        assert_eq!(("12€", "€34"), compartments("12€€34"));
+        // € is shared, then 1 and 3 with 1 the lower priority.
+        assert_eq!("102", part1(&digits, "12€€34\n1331"));
+        assert_eq!("100", part2(&digits, "1€\n€2\n3€", GROUP_SIZE));
     }
 }
@@ -0,0 +1,131 @@
+use std::fmt;
+
+use crate::alphabet::Alphabet;
+use crate::{compartments, groups};
+
+/// Something in the input the puzzle's assumptions do not allow for. Lines
+/// and groups are numbered from 1.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Issue {
+    OddLength { line: usize, len: usize },
+    InvalidItems { line: usize, items: Vec<char> },
+    NoSharedItem { line: usize },
+    SharedItems { line: usize, items: Vec<char> },
+    NoBadge { group: usize, first: usize, last: usize },
+    Badges { group: usize, first: usize, last: usize, items: Vec<char> },
+    IncompleteGroup { group: usize, first: usize, last: usize },
+}
+
+impl fmt::Display for Issue {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::OddLength { line, len } => write!(f, "line {}: odd length {}", line, len),
+            Self::InvalidItems { line, items } => write!(f, "line {}: not item types: {}", line, list(items)),
+            Self::NoSharedItem { line } => write!(f, "line {}: no item in both compartments", line),
+            Self::SharedItems { line, items } => {
+                write!(f, "line {}: several items in both compartments: {}", line, list(items))
+            }
+            Self::NoBadge { group, first, last } => write!(f, "group {} (lines {}-{}): no badge", group, first, last),
+            Self::Badges { group, first, last, items } => {
+                write!(f, "group {} (lines {}-{}): several badges: {}", group, first, last, list(items))
+            }
+            Self::IncompleteGroup { group, first, last } => {
+                write!(f, "group {} (lines {}-{}): incomplete group", group, first, last)
+            }
+        }
+    }
+}
+
+fn list(items: &[char]) -> String {
+    items.iter().map(|c| format!("{:?}", c)).collect::<Vec<_>>().join(", ")
+}
+
+/// Every issue in `input`, rucksacks first, then groups of `group_size`.
+pub fn validate(alphabet: &Alphabet, input: &str, group_size: usize) -> Vec<Issue> {
+    let mut issues = Vec::new();
+    let mut rucksacks = 0;
+    for (i, line) in input.lines().enumerate() {
+        let line_no = i + 1;
+        rucksacks = line_no;
+        let len = line.chars().count();
+        if len % 2 == 1 {
+            issues.push(Issue::OddLength { line: line_no, len });
+        }
+
+        let mut invalid = Vec::new();
+        for c in line.chars().filter(|&c| alphabet.bit(c).is_none()) {
+            if !invalid.contains(&c) {
+                invalid.push(c);
+            }
+        }
+        if !invalid.is_empty() {
+            issues.push(Issue::InvalidItems { line: line_no, items: invalid });
+        }
+
+        let (a, b) = compartments(line);
+        let shared = alphabet.set(a) & alphabet.set(b);
+        match shared.count_ones() {
+            0 => issues.push(Issue::NoSharedItem { line: line_no }),
+            1 => {}
+            _ => issues.push(Issue::SharedItems { line: line_no, items: alphabet.items(shared) }),
+        }
+    }
+
+    let mut complete = 0;
+    for (i, badges) in groups(alphabet, input, group_size).enumerate() {
+        complete = i + 1;
+        let (first, last) = (i * group_size + 1, (i + 1) * group_size);
+        match badges.count_ones() {
+            0 => issues.push(Issue::NoBadge { group: i + 1, first, last }),
+            1 => {}
+            _ => issues.push(Issue::Badges { group: i + 1, first, last, items: alphabet.items(badges) }),
+        }
+    }
+    if rucksacks > complete * group_size {
+        issues.push(Issue::IncompleteGroup {
+            group: complete + 1,
+            first: complete * group_size + 1,
+            last: rucksacks,
+        });
+    }
+    issues
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::INPUT;
+
+    #[test]
+    fn test_valid_input() {
+        assert_eq!(Vec::<Issue>::new(), validate(&Alphabet::letters(), INPUT, 3));
+    }
+
+    #[test]
+    fn test_issues() {
+        let letters = Alphabet::letters();
+        let input = "abcab\nab1ab\nabcabc\nabxy";
+        assert_eq!(
+            vec![
+                Issue::OddLength { line: 1, len: 5 },
+                Issue::SharedItems { line: 1, items: vec!['a', 'b'] },
+                Issue::OddLength { line: 2, len: 5 },
+                Issue::InvalidItems { line: 2, items: vec!['1'] },
+                Issue::SharedItems { line: 2, items: vec!['a', 'b'] },
+                Issue::SharedItems { line: 3, items: vec!['a', 'b', 'c'] },
+                Issue::NoSharedItem { line: 4 },
+                Issue::Badges { group: 1, first: 1, last: 3, items: vec!['a', 'b'] },
+                Issue::IncompleteGroup { group: 2, first: 4, last: 4 },
+            ],
+            validate(&letters, input, 3)
+        );
+
+        let issues = validate(&letters, "abca\nxyzx", 2);
+        assert_eq!(Issue::NoBadge { group: 1, first: 1, last: 2 }, issues[0]);
+        assert_eq!("group 1 (lines 1-2): no badge", issues[0].to_string());
+        assert_eq!(
+            "line 3: several items in both compartments: 'a', 'b', 'c'",
+            validate(&letters, input, 3)[5].to_string()
+        );
+    }
+}
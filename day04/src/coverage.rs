@@ -0,0 +1,140 @@
+use std::fmt;
+
+use crate::interval::{self, Interval};
+
+/// How all assignments together cover the camp's sections.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Coverage {
+    /// From the first assigned section to the last.
+    pub span: Interval,
+    /// Runs of sections within `span` that nobody cleans.
+    pub gaps: Vec<Interval>,
+    /// Runs of sections cleaned by more than one elf, with how many clean
+    /// each of them.
+    pub overlaps: Vec<(Interval, usize)>,
+    pub max_depth: usize,
+    /// Section cleanings handed out, counting a section once per elf.
+    pub assigned: u64,
+    /// Distinct sections cleaned by anyone.
+    pub covered: u64,
+}
+
+impl Coverage {
+    /// Cleanings beyond the first of each section.
+    pub fn wasted(&self) -> u64 {
+        self.assigned - self.covered
+    }
+}
+
+/// Splits the span of `assignments` into maximal runs cleaned by the same
+/// number of elves, in order, by sweeping over their start and end points.
+pub fn profile(assignments: &[Interval]) -> Vec<(Interval, usize)> {
+    let mut events = assignments
+        .iter()
+        .filter(|a| !a.is_empty())
+        .flat_map(|a| [(a.start(), 1), (a.end(), -1)])
+        .collect::<Vec<(u64, i64)>>();
+    events.sort_unstable();
+
+    let mut runs: Vec<(Interval, usize)> = Vec::new();
+    let mut depth = 0i64;
+    let mut events = events.into_iter().peekable();
+    while let Some((at, change)) = events.next() {
+        depth += change;
+        // Apply every event at the same point before emitting a run.
+        if events.peek().is_some_and(|&(next, _)| next == at) {
+            continue;
+        }
+        let Some(&(next, _)) = events.peek() else {
+            break;
+        };
+        let run = Interval::half_open(at, next);
+        match runs.last_mut() {
+            Some((last, d)) if *d == depth as usize && last.end() == at => *last = last.hull(&run),
+            _ => runs.push((run, depth as usize)),
+        }
+    }
+    runs
+}
+
+pub fn sweep(assignments: &[Interval]) -> Coverage {
+    let runs = profile(assignments);
+    let span = runs.iter().fold(Interval::EMPTY, |span, (run, _)| span.hull(run));
+
+    Coverage {
+        span,
+        gaps: runs.iter().filter(|&&(_, d)| d == 0).map(|&(run, _)| run).collect(),
+        overlaps: runs.iter().filter(|&&(_, d)| d > 1).copied().collect(),
+        max_depth: runs.iter().map(|&(_, d)| d).max().unwrap_or(0),
+        assigned: assignments.iter().map(Interval::len).sum(),
+        covered: interval::merge(assignments.iter().copied()).iter().map(Interval::len).sum(),
+    }
+}
+
+fn sections(n: u64) -> String {
+    format!("{} section{}", n, if n == 1 { "" } else { "s" })
+}
+
+impl fmt::Display for Coverage {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "sections {}: {} covered, {} uncovered", self.span, self.covered, self.span.len() - self.covered)?;
+        for gap in &self.gaps {
+            writeln!(f, "  nobody cleans {:<11} ({})", gap.to_string(), sections(gap.len()))?;
+        }
+        for (run, depth) in &self.overlaps {
+            writeln!(f, "  {} elves clean {:<11} ({})", depth, run.to_string(), sections(run.len()))?;
+        }
+        writeln!(f, "max depth: {}", self.max_depth)?;
+        writeln!(
+            f,
+            "wasted effort: {} of {} section cleanings",
+            self.wasted(),
+            self.assigned
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_profile() {
+        let assignments = [Interval::closed(2, 4), Interval::closed(6, 8), Interval::closed(3, 7)];
+        assert_eq!(
+            vec![
+                (Interval::closed(2, 2), 1),
+                (Interval::closed(3, 4), 2),
+                (Interval::closed(5, 5), 1),
+                (Interval::closed(6, 7), 2),
+                (Interval::closed(8, 8), 1),
+            ],
+            profile(&assignments)
+        );
+        // Back to back assignments leave no zero-length run between them.
+        assert_eq!(
+            vec![(Interval::closed(1, 5), 1)],
+            profile(&[Interval::closed(1, 2), Interval::closed(3, 5)])
+        );
+        assert_eq!(Vec::<(Interval, usize)>::new(), profile(&[]));
+    }
+
+    #[test]
+    fn test_sweep() {
+        let assignments = [
+            Interval::closed(2, 4),
+            Interval::closed(2, 4),
+            Interval::closed(3, 3),
+            Interval::closed(7, 9),
+        ];
+        let coverage = sweep(&assignments);
+        assert_eq!(Interval::closed(2, 9), coverage.span);
+        assert_eq!(vec![Interval::closed(5, 6)], coverage.gaps);
+        assert_eq!(
+            vec![(Interval::closed(2, 2), 2), (Interval::closed(3, 3), 3), (Interval::closed(4, 4), 2)],
+            coverage.overlaps
+        );
+        assert_eq!(3, coverage.max_depth);
+        assert_eq!((10, 6, 4), (coverage.assigned, coverage.covered, coverage.wasted()));
+    }
+}
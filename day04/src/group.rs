@@ -0,0 +1,146 @@
+use std::fmt;
+
+use crate::interval::{self, by_start, Interval};
+
+/// The assignments on one line of the input.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Group {
+    pub assignments: Vec<Interval>,
+}
+
+impl Group {
+    pub fn new(assignments: Vec<Interval>) -> Self {
+        Group { assignments }
+    }
+
+    /// Some assignment fully containing another, as `(container, contained)`
+    /// positions in `assignments`.
+    ///
+    /// Once sorted by start, longest first, an assignment is contained in an
+    /// earlier one exactly when it ends no later than the furthest end so far.
+    /// An empty assignment, such as a reversed range, is contained in any
+    /// other, as with [`Interval::contains_interval`].
+    pub fn containing_pair(&self) -> Option<(usize, usize)> {
+        if let Some(empty) = self.assignments.iter().position(Interval::is_empty) {
+            let container = (0..self.assignments.len()).find(|&i| i != empty)?;
+            return Some((container, empty));
+        }
+        let mut furthest: Option<usize> = None;
+        for i in by_start(&self.assignments) {
+            if let Some(f) = furthest {
+                if self.assignments[i].end() <= self.assignments[f].end() {
+                    return Some((f, i));
+                }
+            }
+            if furthest.is_none_or(|f| self.assignments[i].end() > self.assignments[f].end()) {
+                furthest = Some(i);
+            }
+        }
+        None
+    }
+
+    /// Some two assignments sharing a section, as positions in `assignments`.
+    pub fn overlapping_pair(&self) -> Option<(usize, usize)> {
+        let mut furthest: Option<usize> = None;
+        for i in by_start(&self.assignments) {
+            if let Some(f) = furthest {
+                if self.assignments[i].overlaps(&self.assignments[f]) {
+                    return Some((f, i));
+                }
+            }
+            if furthest.is_none_or(|f| self.assignments[i].end() > self.assignments[f].end()) {
+                furthest = Some(i);
+            }
+        }
+        None
+    }
+
+    /// The sections every elf in the group cleans.
+    pub fn common(&self) -> Interval {
+        let mut all = self.assignments.iter();
+        let first = all.next().copied().unwrap_or(Interval::EMPTY);
+        all.fold(first, |common, a| common.intersection(a))
+    }
+
+    /// The sections anyone in the group cleans.
+    pub fn union(&self) -> Vec<Interval> {
+        interval::merge(self.assignments.iter().copied())
+    }
+}
+
+impl fmt::Display for Group {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = |i: usize| format!("#{} {}", i + 1, self.assignments[i]);
+        let union = self.union();
+        write!(
+            f,
+            "contains: {:<20} overlaps: {:<20} common: {:<7} union: {} ({} sections)",
+            self.containing_pair().map_or("-".to_string(), |(a, b)| format!("{} > {}", name(a), name(b))),
+            self.overlapping_pair().map_or("-".to_string(), |(a, b)| format!("{} & {}", name(a), name(b))),
+            self.common().to_string(),
+            union.iter().map(Interval::to_string).collect::<Vec<_>>().join(" "),
+            union.iter().map(Interval::len).sum::<u64>()
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn group(ranges: &[(u64, u64)]) -> Group {
+        Group::new(ranges.iter().map(|&(a, b)| Interval::closed(a, b)).collect())
+    }
+
+    #[test]
+    fn test_pairs() {
+        let g = group(&[(2, 4), (6, 8), (3, 5)]);
+        assert_eq!(None, g.containing_pair());
+        assert_eq!(Some((0, 2)), g.overlapping_pair());
+
+        let g = group(&[(1, 2), (6, 6), (4, 9)]);
+        assert_eq!(Some((2, 1)), g.containing_pair());
+        assert_eq!(Some((2, 1)), g.overlapping_pair());
+
+        // Identical ranges contain each other.
+        assert_eq!(Some((0, 1)), group(&[(3, 3), (3, 3)]).containing_pair());
+        assert_eq!(None, group(&[(1, 2), (3, 4), (5, 6)]).overlapping_pair());
+        assert_eq!(None, group(&[(1, 2)]).overlapping_pair());
+
+        // A reversed range is empty, and contained in any other range.
+        assert_eq!(Some((1, 0)), group(&[(5, 3), (1, 9)]).containing_pair());
+        assert_eq!(None, group(&[(5, 3)]).containing_pair());
+    }
+
+    #[test]
+    fn test_matches_every_pair() {
+        let ranges = [(1, 5), (2, 3), (4, 9), (6, 7), (8, 8), (10, 12), (11, 11), (3, 10), (5, 3)];
+        for a in 0..ranges.len() {
+            for b in a..ranges.len() {
+                for c in b..ranges.len() {
+                    let g = group(&[ranges[a], ranges[b], ranges[c]]);
+                    let pairs = [(0, 1), (0, 2), (1, 2), (1, 0), (2, 0), (2, 1)];
+                    let contains = pairs.iter().any(|&(x, y)| g.assignments[x].contains_interval(&g.assignments[y]));
+                    let overlaps = pairs.iter().any(|&(x, y)| g.assignments[x].overlaps(&g.assignments[y]));
+                    assert_eq!(contains, g.containing_pair().is_some(), "{:?}", g);
+                    assert_eq!(overlaps, g.overlapping_pair().is_some(), "{:?}", g);
+                    if let Some((x, y)) = g.containing_pair() {
+                        assert!(g.assignments[x].contains_interval(&g.assignments[y]));
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_common_and_union() {
+        let g = group(&[(2, 8), (4, 6), (5, 9)]);
+        assert_eq!(Interval::closed(5, 6), g.common());
+        assert_eq!(vec![Interval::closed(2, 9)], g.union());
+        assert_eq!(Interval::EMPTY, group(&[(1, 2), (3, 4)]).common());
+        assert_eq!(
+            "contains: #1 2-8 > #2 4-6      overlaps: #1 2-8 & #2 4-6      common: 5-6     union: 2-9 (8 sections)",
+            g.to_string()
+        );
+    }
+}
@@ -0,0 +1,140 @@
+use crate::interval::Interval;
+
+/// All assignments sorted by start, laid out as an implicit balanced search
+/// tree: the middle of every range is its root. Each root also records the
+/// furthest end in its range, so a query can skip whole subtrees that end
+/// before it starts. A query for `k` matches takes `O(min(n, k log n))`:
+/// each match may cost a walk down the tree, but no node is visited twice.
+#[derive(Debug, Clone)]
+pub struct Index {
+    /// Assignments with the number of the elf they belong to.
+    entries: Vec<(Interval, usize)>,
+    max_end: Vec<u64>,
+    /// The assignment of each elf, by elf number minus one.
+    by_elf: Vec<Interval>,
+}
+
+impl Index {
+    /// Indexes `assignments`, numbering elves from 1 in order.
+    pub fn new(assignments: &[Interval]) -> Self {
+        let mut entries = assignments.iter().copied().zip(1..).collect::<Vec<_>>();
+        entries.sort_unstable_by_key(|(a, elf)| (a.start(), *elf));
+        let mut max_end = vec![0; entries.len()];
+        Self::build(&entries, &mut max_end, 0, entries.len());
+        Index { entries, max_end, by_elf: assignments.to_vec() }
+    }
+
+    fn build(entries: &[(Interval, usize)], max_end: &mut [u64], lo: usize, hi: usize) -> u64 {
+        if lo >= hi {
+            return 0;
+        }
+        let mid = lo + (hi - lo) / 2;
+        let left = Self::build(entries, max_end, lo, mid);
+        let right = Self::build(entries, max_end, mid + 1, hi);
+        max_end[mid] = entries[mid].0.end().max(left).max(right);
+        max_end[mid]
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// The assignment of elf `elf`, numbered from 1.
+    pub fn assignment(&self, elf: usize) -> Option<Interval> {
+        self.by_elf.get(elf.checked_sub(1)?).copied()
+    }
+
+    /// Every assignment sharing a section with `query`, ordered by elf.
+    pub fn overlapping(&self, query: &Interval) -> Vec<(usize, Interval)> {
+        let mut found = Vec::new();
+        if !query.is_empty() {
+            self.collect(query, 0, self.entries.len(), &mut found);
+        }
+        found.sort_unstable();
+        found
+    }
+
+    fn collect(&self, query: &Interval, lo: usize, hi: usize, found: &mut Vec<(usize, Interval)>) {
+        if lo >= hi {
+            return;
+        }
+        let mid = lo + (hi - lo) / 2;
+        if self.max_end[mid] <= query.start() {
+            return;
+        }
+        self.collect(query, lo, mid, found);
+        let (assignment, elf) = self.entries[mid];
+        // Everything from here on starts no earlier, so once past the query
+        // the right subtree cannot match.
+        if assignment.start() < query.end() {
+            if assignment.overlaps(query) {
+                found.push((elf, assignment));
+            }
+            self.collect(query, mid + 1, hi, found);
+        }
+    }
+
+    /// Who cleans `section`.
+    pub fn stab(&self, section: u32) -> Vec<(usize, Interval)> {
+        self.overlapping(&Interval::closed(section.into(), section.into()))
+    }
+
+    /// The other elves whose assignments share a section with elf `elf`'s,
+    /// or `None` if there is no such elf.
+    pub fn overlaps(&self, elf: usize) -> Option<Vec<(usize, Interval)>> {
+        let assignment = self.assignment(elf)?;
+        let mut found = self.overlapping(&assignment);
+        found.retain(|&(other, _)| other != elf);
+        Some(found)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_queries() {
+        let assignments = [Interval::closed(2, 4), Interval::closed(6, 8), Interval::closed(3, 7), Interval::closed(9, 9)];
+        let index = Index::new(&assignments);
+        assert_eq!(4, index.len());
+        assert_eq!(vec![(1, assignments[0]), (3, assignments[2])], index.stab(4));
+        assert_eq!(vec![(4, assignments[3])], index.stab(9));
+        assert!(index.stab(10).is_empty());
+        assert!(index.stab(u32::MAX).is_empty());
+
+        assert_eq!(Some(vec![(1, assignments[0]), (2, assignments[1])]), index.overlaps(3));
+        assert_eq!(Some(vec![]), index.overlaps(4));
+        assert_eq!(None, index.overlaps(5));
+        assert_eq!(None, index.overlaps(0));
+    }
+
+    #[test]
+    fn test_matches_a_linear_scan() {
+        // A small linear congruential generator keeps the test deterministic.
+        let mut state = 12345u64;
+        let mut next = |bound: u64| {
+            state = state.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+            (state >> 33) % bound
+        };
+        let assignments = (0..2000)
+            .map(|_| {
+                let start = next(10_000);
+                Interval::closed(start, start + next(300))
+            })
+            .collect::<Vec<_>>();
+        let index = Index::new(&assignments);
+
+        for _ in 0..200 {
+            let start = next(10_500);
+            let query = Interval::closed(start, start + next(50));
+            let expected = assignments
+                .iter()
+                .enumerate()
+                .filter(|(_, a)| a.overlaps(&query))
+                .map(|(i, &a)| (i + 1, a))
+                .collect::<Vec<_>>();
+            assert_eq!(expected, index.overlapping(&query));
+        }
+    }
+}
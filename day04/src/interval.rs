@@ -0,0 +1,245 @@
+use std::cmp;
+use std::fmt;
+
+/// A contiguous range of section IDs, stored half-open as `[start, end)`.
+///
+/// Every empty interval is stored as `[0, 0)` so empty intervals compare
+/// equal however they were made.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Default)]
+pub struct Interval {
+    start: u64,
+    end: u64,
+}
+
+impl Interval {
+    pub const EMPTY: Interval = Interval { start: 0, end: 0 };
+
+    /// The sections `start..end`, empty when `end <= start`.
+    pub fn half_open(start: u64, end: u64) -> Self {
+        if start < end {
+            Interval { start, end }
+        } else {
+            Self::EMPTY
+        }
+    }
+
+    /// The sections `start..=last`, empty when `last < start`.
+    pub fn closed(start: u64, last: u64) -> Self {
+        match last.checked_add(1) {
+            Some(end) => Self::half_open(start, end),
+            None => panic!("closed interval ending at u64::MAX"),
+        }
+    }
+
+    pub fn start(&self) -> u64 {
+        self.start
+    }
+
+    /// The first section after the interval.
+    pub fn end(&self) -> u64 {
+        self.end
+    }
+
+    /// The last section in the interval, if any.
+    pub fn last(&self) -> Option<u64> {
+        (!self.is_empty()).then(|| self.end - 1)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.start == self.end
+    }
+
+    pub fn len(&self) -> u64 {
+        self.end - self.start
+    }
+
+    /// Whether every section of `other` is also in `self`. The empty
+    /// interval is in every interval.
+    pub fn contains_interval(&self, other: &Interval) -> bool {
+        other.is_empty() || (self.start <= other.start && other.end <= self.end)
+    }
+
+    pub fn overlaps(&self, other: &Interval) -> bool {
+        !self.intersection(other).is_empty()
+    }
+
+    /// Whether `other` starts right where `self` ends or the other way
+    /// round, so together they cover a contiguous range without overlapping.
+    pub fn is_adjacent(&self, other: &Interval) -> bool {
+        !self.is_empty() && !other.is_empty() && (self.end == other.start || other.end == self.start)
+    }
+
+    pub fn intersection(&self, other: &Interval) -> Interval {
+        Self::half_open(cmp::max(self.start, other.start), cmp::min(self.end, other.end))
+    }
+
+    /// The smallest interval containing both.
+    pub fn hull(&self, other: &Interval) -> Interval {
+        match (self.is_empty(), other.is_empty()) {
+            (true, _) => *other,
+            (_, true) => *self,
+            _ => Interval { start: cmp::min(self.start, other.start), end: cmp::max(self.end, other.end) },
+        }
+    }
+
+    /// The sections in either interval, or `None` when they leave a gap and
+    /// so are not a single interval.
+    pub fn union(&self, other: &Interval) -> Option<Interval> {
+        let hull = self.hull(other);
+        (hull.len() <= self.len() + other.len()).then_some(hull)
+    }
+
+    /// The sections of `self` not in `other`: up to two pieces, before and
+    /// after `other`, empty pieces left out.
+    pub fn difference(&self, other: &Interval) -> Vec<Interval> {
+        if !self.overlaps(other) {
+            return if self.is_empty() { vec![] } else { vec![*self] };
+        }
+        [Self::half_open(self.start, other.start), Self::half_open(other.end, self.end)]
+            .into_iter()
+            .filter(|piece| !piece.is_empty())
+            .collect()
+    }
+}
+
+/// Merges `intervals` into the fewest disjoint intervals covering the same
+/// sections, in order. Overlapping and adjacent intervals are combined.
+pub fn merge(intervals: impl IntoIterator<Item = Interval>) -> Vec<Interval> {
+    let mut sorted = intervals.into_iter().filter(|i| !i.is_empty()).collect::<Vec<_>>();
+    sorted.sort_unstable();
+
+    let mut merged: Vec<Interval> = Vec::with_capacity(sorted.len());
+    for interval in sorted {
+        match merged.last_mut() {
+            Some(last) if interval.start <= last.end => last.end = cmp::max(last.end, interval.end),
+            _ => merged.push(interval),
+        }
+    }
+    merged
+}
+
+/// Positions in `intervals` sorted the way a sweep meets them: by start,
+/// longest first, ties in their original order.
+pub fn by_start(intervals: &[Interval]) -> Vec<usize> {
+    let mut order = (0..intervals.len()).collect::<Vec<_>>();
+    order.sort_by_key(|&i| (intervals[i].start, cmp::Reverse(intervals[i].end)));
+    order
+}
+
+/// Closed notation as in the puzzle input, `2-4`.
+impl fmt::Display for Interval {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.last() {
+            Some(last) => write!(f, "{}-{}", self.start, last),
+            None => write!(f, "empty"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    /// Small bounds so overlaps, adjacency and gaps all come up often.
+    const MAX: u64 = 40;
+
+    fn interval() -> impl Strategy<Value = Interval> {
+        (0..MAX, 0..MAX).prop_map(|(a, b)| Interval::half_open(a, b))
+    }
+
+    fn sections(interval: &Interval) -> Vec<u64> {
+        (interval.start..interval.end).collect()
+    }
+
+    #[test]
+    fn test_constructors() {
+        assert_eq!(Interval::half_open(2, 5), Interval::closed(2, 4));
+        assert_eq!(3, Interval::closed(2, 4).len());
+        assert_eq!(Some(4), Interval::closed(2, 4).last());
+        assert_eq!(1, Interval::closed(6, 6).len());
+        assert_eq!(Interval::EMPTY, Interval::closed(5, 4));
+        assert_eq!(Interval::EMPTY, Interval::half_open(7, 7));
+        assert_eq!("2-4", Interval::closed(2, 4).to_string());
+        assert_eq!("empty", Interval::EMPTY.to_string());
+    }
+
+    #[test]
+    fn test_operations() {
+        let a = Interval::closed(2, 6);
+        let b = Interval::closed(4, 8);
+        assert_eq!(Interval::closed(4, 6), a.intersection(&b));
+        assert_eq!(Some(Interval::closed(2, 8)), a.union(&b));
+        assert_eq!(vec![Interval::closed(2, 3)], a.difference(&b));
+        assert_eq!(
+            vec![Interval::closed(2, 3), Interval::closed(6, 8)],
+            Interval::closed(2, 8).difference(&Interval::closed(4, 5))
+        );
+
+        let c = Interval::closed(7, 9);
+        assert!(a.is_adjacent(&c) && c.is_adjacent(&a));
+        assert!(!a.overlaps(&c));
+        assert_eq!(Some(Interval::closed(2, 9)), a.union(&c));
+        assert_eq!(None, a.union(&Interval::closed(8, 9)));
+
+        assert_eq!(vec![2, 0, 1, 3], by_start(&[a, b, Interval::closed(2, 9), c]));
+        assert_eq!(
+            vec![Interval::closed(1, 9), Interval::closed(11, 12)],
+            merge([c, Interval::closed(11, 12), a, Interval::closed(1, 1), Interval::EMPTY])
+        );
+    }
+
+    proptest! {
+        #[test]
+        fn prop_intersection(a in interval(), b in interval()) {
+            let both = a.intersection(&b);
+            prop_assert_eq!(both, b.intersection(&a));
+            prop_assert!(a.contains_interval(&both) && b.contains_interval(&both));
+            let expected = sections(&a).into_iter().filter(|s| sections(&b).contains(s)).collect::<Vec<_>>();
+            prop_assert_eq!(expected, sections(&both));
+            prop_assert_eq!(a.overlaps(&b), !both.is_empty());
+        }
+
+        #[test]
+        fn prop_union(a in interval(), b in interval()) {
+            let either = sections(&a).into_iter().chain(sections(&b)).collect::<std::collections::BTreeSet<_>>();
+            let contiguous = either.iter().zip(either.iter().skip(1)).all(|(x, y)| x + 1 == *y);
+            match a.union(&b) {
+                Some(union) => {
+                    prop_assert_eq!(either.into_iter().collect::<Vec<_>>(), sections(&union));
+                    prop_assert_eq!(a.len() + b.len(), union.len() + a.intersection(&b).len());
+                }
+                None => prop_assert!(!contiguous),
+            }
+            if a.is_adjacent(&b) {
+                prop_assert!(!a.overlaps(&b) && a.union(&b).is_some());
+            }
+        }
+
+        #[test]
+        fn prop_difference(a in interval(), b in interval()) {
+            let pieces = a.difference(&b);
+            let expected = sections(&a).into_iter().filter(|s| !sections(&b).contains(s)).collect::<Vec<_>>();
+            prop_assert_eq!(expected, pieces.iter().flat_map(sections).collect::<Vec<_>>());
+            prop_assert!(pieces.iter().all(|p| !p.is_empty() && !p.overlaps(&b)));
+        }
+
+        #[test]
+        fn prop_closed_and_half_open(start in 0..MAX, last in 0..MAX) {
+            prop_assert_eq!(Interval::closed(start, last), Interval::half_open(start, last + 1));
+            prop_assert_eq!((start..=last).collect::<Vec<_>>(), sections(&Interval::closed(start, last)));
+        }
+
+        #[test]
+        fn prop_merge(intervals in proptest::collection::vec(interval(), 0..8)) {
+            let merged = merge(intervals.clone());
+            let mut expected = intervals.iter().flat_map(sections).collect::<Vec<_>>();
+            expected.sort_unstable();
+            expected.dedup();
+            prop_assert_eq!(expected, merged.iter().flat_map(sections).collect::<Vec<_>>());
+            for pair in merged.windows(2) {
+                prop_assert!(pair[0].end < pair[1].start);
+            }
+        }
+    }
+}
@@ -1,5 +1,4 @@
 use std::{env, fs, io, path::Path};
-use std::cmp;
 use std::convert::TryFrom;
 use std::num::ParseIntError;
 
@@ -7,40 +6,85 @@ use nom::IResult;
 use nom::character::complete;
 use nom::character::complete::digit1;
 use nom::combinator::map_res;
+use nom::multi::separated_list1;
 use nom::sequence::separated_pair;
 
+mod coverage;
+mod group;
+mod index;
+mod interval;
+mod reassign;
+
+use group::Group;
+use index::Index;
+use interval::Interval;
+
 fn main() -> io::Result<()> {
     let mut dir = env::current_exe()?;
     dir.pop();
     let dir = dir.join(Path::new("../../day04/input.txt"));
 
     let input = fs::read_to_string(dir)?;
-    println!("part1: {}", part1(&input));
-    println!("part2: {}", part2(&input));
+    let args = env::args().skip(1).collect::<Vec<_>>();
+    // Sections are read as u32 like the assignments, so any section given
+    // here has a closed interval of its own.
+    let number = |what: &str| {
+        args.get(1).and_then(|n| n.parse::<u32>().ok()).ok_or_else(|| {
+            let usage = format!("usage: {} <{}>", args[0], what);
+            io::Error::new(io::ErrorKind::InvalidInput, usage)
+        })
+    };
+    match args.first().map(String::as_str) {
+        Some("groups") => {
+            for (i, line) in input.lines().enumerate() {
+                let (_, group) = parse_line(line).unwrap();
+                println!("line {:<5} {}", i + 1, group);
+            }
+        }
+        Some("relations") => print!("{}", relations(&input)),
+        Some("coverage") => print!("{}", coverage::sweep(&assignments(&input))),
+        Some("reassign") => print!("{}", reassign::plan(&assignments(&input))),
+        Some("who") => {
+            let section = number("section")?;
+            let index = Index::new(&assignments(&input));
+            let found = index.stab(section);
+            println!("section {}: {} elves", section, found.len());
+            for (elf, assignment) in found {
+                println!("  elf {:<5} {}", elf, assignment);
+            }
+        }
+        Some("overlaps") => {
+            let elf = number("elf")? as usize;
+            let index = Index::new(&assignments(&input));
+            let found = index.overlaps(elf).ok_or_else(|| {
+                let error = format!("no elf {}, elves are numbered 1 to {}", elf, index.len());
+                io::Error::new(io::ErrorKind::InvalidInput, error)
+            })?;
+            let assignment = index.assignment(elf).unwrap_or_default();
+            println!("elf {} ({}) overlaps {} other elves", elf, assignment, found.len());
+            for (other, assignment) in found {
+                println!("  elf {:<5} {}", other, assignment);
+            }
+        }
+        _ => {
+            println!("part1: {}", part1(&input));
+            println!("part2: {}", part2(&input));
+        }
+    }
     Ok(())
 }
 
 #[derive(Debug)]
 struct Schedule {
-    start: u32,
-    end: u32,
-}
-
-impl Schedule {
-    fn contains(&self, other: &Schedule) -> bool {
-        self.start <= other.start && self.end >= other.end
-    }
-
-    fn overlaps(&self, other: &Schedule) -> bool {
-        (cmp::max(self.start, other.start) as i32) - (cmp::min(self.end, other.end) as i32) <= 0
-    }
+    sections: Interval,
 }
 
 impl TryFrom<(&str, &str)> for Schedule {
     type Error = ParseIntError;
 
     fn try_from(input: (&str, &str)) -> Result<Self, Self::Error> {
-        Ok(Schedule { start: input.0.parse()?, end: input.1.parse()? })
+        let (start, last) = (input.0.parse::<u32>()?, input.1.parse::<u32>()?);
+        Ok(Schedule { sections: Interval::closed(start.into(), last.into()) })
     }
 }
 
@@ -49,19 +93,19 @@ fn parse_schedule(input: &str) -> IResult<&str, Schedule> {
     Ok((input, schedule))
 }
 
-fn parse_line(input: &str) -> IResult<&str, (Schedule, Schedule)> {
-    let (input, schedules) = separated_pair(parse_schedule, complete::char(','), parse_schedule)(input)?;
-    Ok((input, schedules))
+fn parse_line(input: &str) -> IResult<&str, Group> {
+    let (input, schedules) = separated_list1(complete::char(','), parse_schedule)(input)?;
+    Ok((input, Group::new(schedules.into_iter().map(|s| s.sections).collect())))
 }
 
 fn part1(input: &str) -> String {
     let count = input.lines()
         .map(|line| {
-            let (_, schedules) = parse_line(line).unwrap();
-            schedules
+            let (_, group) = parse_line(line).unwrap();
+            group
         })
-        .map(|schedules| {
-            if schedules.0.contains(&schedules.1) || schedules.1.contains(&schedules.0) { 1 } else { 0 }
+        .map(|group| {
+            if group.containing_pair().is_some() { 1 } else { 0 }
         })
         .sum::<u32>();
 
@@ -71,17 +115,82 @@ fn part1(input: &str) -> String {
 fn part2(input: &str) -> String {
     let count = input.lines()
         .map(|line| {
-            let (_, schedules) = parse_line(line).unwrap();
-            schedules
+            let (_, group) = parse_line(line).unwrap();
+            group
         })
-        .map(|schedules| {
-            if schedules.0.overlaps(&schedules.1) { 1 } else { 0 }
+        .map(|group| {
+            if group.overlapping_pair().is_some() { 1 } else { 0 }
         })
         .sum::<u32>();
 
     count.to_string()
 }
 
+/// Every assignment in the file, in order, so elf `n` has the `n`th
+/// assignment counting from 1: with two per line, the elves on line 1 are 1
+/// and 2, and so on.
+fn assignments(input: &str) -> Vec<Interval> {
+    input
+        .lines()
+        .flat_map(|line| {
+            let (_, group) = parse_line(line).unwrap();
+            group.assignments
+        })
+        .collect()
+}
+
+fn list(intervals: &[Interval]) -> String {
+    if intervals.is_empty() {
+        "-".to_string()
+    } else {
+        intervals.iter().map(Interval::to_string).collect::<Vec<_>>().join(" ")
+    }
+}
+
+/// How every two assignments on each line relate, then the sections
+/// assigned to anyone at all.
+fn relations(input: &str) -> String {
+    let mut out = String::new();
+    let mut all = Vec::new();
+    let pairs = input.lines().flat_map(|line| {
+        let (_, group) = parse_line(line).unwrap();
+        let a = &group.assignments;
+        (0..a.len()).flat_map(|i| (i + 1..a.len()).map(move |j| (a[i], a[j]))).collect::<Vec<_>>()
+    });
+    for (a, b) in pairs {
+        let shared = a.intersection(&b);
+        let union = a.union(&b).map_or("-".to_string(), |u| u.to_string());
+        let relation = if a.contains_interval(&b) || b.contains_interval(&a) {
+            "contains"
+        } else if a.overlaps(&b) {
+            "overlaps"
+        } else if a.is_adjacent(&b) {
+            "adjacent"
+        } else {
+            "disjoint"
+        };
+        out += &format!(
+            "{:<13} {:<8} shared: {:<9} ({:>2}) union: {:<9} first only: {:<13} second only: {}\n",
+            format!("{},{}", a, b),
+            relation,
+            shared.to_string(),
+            shared.len(),
+            union,
+            list(&a.difference(&b)),
+            list(&b.difference(&a))
+        );
+        all.extend([a, b]);
+    }
+
+    let covered = interval::merge(all);
+    out += &format!(
+        "{} sections covered by {}\n",
+        covered.iter().map(Interval::len).sum::<u64>(),
+        list(&covered)
+    );
+    out
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -96,10 +205,48 @@ mod tests {
     #[test]
     fn test_part1() {
         assert_eq!("2", part1(INPUT));
+        // Counted by the original pairwise comparison too.
+        assert_eq!("1", part1("5-3,1-9"));
     }
 
     #[test]
     fn test_part2() {
         assert_eq!("4", part2(INPUT));
     }
+
+    #[test]
+    fn test_groups() {
+        let input = "2-4,6-8,3-5\n1-9,2-3,5-5,7-8\n1-1,3-3";
+        assert_eq!("1", part1(input));
+        assert_eq!("2", part2(input));
+
+        let (_, group) = parse_line("1-9,2-3,5-5,7-8").unwrap();
+        assert_eq!(4, group.assignments.len());
+        assert_eq!(Interval::EMPTY, group.common());
+        assert_eq!(vec![Interval::closed(1, 9)], group.union());
+        assert_eq!(9, assignments(input).len());
+        // Three pairs, six pairs and one pair, then the summary.
+        assert_eq!(3 + 6 + 1 + 1, relations(input).lines().count());
+    }
+
+    #[test]
+    fn test_relations() {
+        let out = relations(INPUT);
+        let lines = out.lines().collect::<Vec<_>>();
+        assert!(lines[0].starts_with("2-4,6-8       disjoint shared: empty     ( 0) union: -"));
+        assert!(lines[1].starts_with("2-3,4-5       adjacent shared: empty     ( 0) union: 2-5"));
+        assert!(lines[3].ends_with("first only: 2-2 8-8       second only: -"));
+        assert_eq!("8 sections covered by 2-9", lines[6]);
+    }
+
+    #[test]
+    fn test_coverage() {
+        let coverage = coverage::sweep(&assignments(INPUT));
+        assert_eq!(Interval::closed(2, 9), coverage.span);
+        assert!(coverage.gaps.is_empty());
+        // Section 6 is in eight assignments.
+        assert_eq!(8, coverage.max_depth);
+        assert_eq!(8, coverage.covered);
+        assert_eq!(3 + 3 + 2 + 2 + 3 + 3 + 7 + 5 + 1 + 3 + 5 + 5 - 8, coverage.wasted());
+    }
 }
@@ -0,0 +1,139 @@
+use std::fmt;
+
+use crate::interval::{by_start, Interval};
+
+/// How to remove duplicated work from a set of assignments.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Plan {
+    pub assignments: Vec<Interval>,
+    /// The largest set of elves whose assignments do not overlap, by
+    /// number. Everyone else must be reassigned for no section to be
+    /// cleaned twice without changing any range.
+    pub kept: Vec<usize>,
+    /// A new range for every elf covering exactly the same sections with no
+    /// overlap, by elf number minus one. `None` frees the elf entirely.
+    pub trimmed: Vec<Option<Interval>>,
+}
+
+impl Plan {
+    /// Elves to reassign if assignments may only be kept or dropped whole.
+    pub fn reassigned(&self) -> usize {
+        self.assignments.len() - self.kept.len()
+    }
+}
+
+pub fn plan(assignments: &[Interval]) -> Plan {
+    // Interval scheduling: always keeping the assignment that finishes first
+    // leaves the most room for the rest, which is optimal.
+    let mut by_end = (0..assignments.len()).filter(|&i| !assignments[i].is_empty()).collect::<Vec<_>>();
+    by_end.sort_by_key(|&i| (assignments[i].end(), i));
+    let mut kept = Vec::new();
+    let mut free_from = 0;
+    for i in by_end {
+        if assignments[i].start() >= free_from {
+            kept.push(i + 1);
+            free_from = assignments[i].end();
+        }
+    }
+    kept.sort_unstable();
+
+    // Sweeping by start, each elf keeps only what nobody before them covers.
+    // What is left is contiguous since earlier elves cover everything up to
+    // `covered`.
+    let mut trimmed = vec![None; assignments.len()];
+    let mut covered = 0;
+    for i in by_start(assignments) {
+        let rest = Interval::half_open(assignments[i].start().max(covered), assignments[i].end());
+        if !rest.is_empty() {
+            trimmed[i] = Some(rest);
+            covered = rest.end();
+        }
+    }
+
+    Plan { assignments: assignments.to_vec(), kept, trimmed }
+}
+
+impl fmt::Display for Plan {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let n = self.assignments.len();
+        writeln!(
+            f,
+            "{} of {} assignments can stand without overlap: reassign at least {} elves",
+            self.kept.len(),
+            n,
+            self.reassigned()
+        )?;
+
+        let freed = self.trimmed.iter().filter(|t| t.is_none()).count();
+        let changed = self.assignments.iter().zip(&self.trimmed).filter(|(a, t)| t.is_some_and(|t| t != **a)).count();
+        writeln!(
+            f,
+            "trimming instead keeps every section covered once: {} unchanged, {} trimmed, {} freed",
+            n - freed - changed,
+            changed,
+            freed
+        )?;
+        for (elf, (assignment, trimmed)) in self.assignments.iter().zip(&self.trimmed).enumerate() {
+            match trimmed {
+                Some(t) if t == assignment => {}
+                Some(t) => writeln!(f, "  elf {:<5} {:<7} -> {}", elf + 1, assignment.to_string(), t)?,
+                None => writeln!(f, "  elf {:<5} {:<7} -> freed", elf + 1, assignment.to_string())?,
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::interval;
+
+    #[test]
+    fn test_plan() {
+        let assignments = [
+            Interval::closed(2, 8),
+            Interval::closed(3, 7),
+            Interval::closed(6, 9),
+            Interval::closed(9, 12),
+            Interval::closed(15, 16),
+        ];
+        let plan = plan(&assignments);
+        assert_eq!(vec![2, 4, 5], plan.kept);
+        assert_eq!(2, plan.reassigned());
+        assert_eq!(
+            vec![
+                Some(Interval::closed(2, 8)),
+                None,
+                Some(Interval::closed(9, 9)),
+                Some(Interval::closed(10, 12)),
+                Some(Interval::closed(15, 16)),
+            ],
+            plan.trimmed
+        );
+    }
+
+    #[test]
+    fn test_trimming_keeps_coverage_without_overlap() {
+        let assignments = [
+            Interval::closed(2, 4),
+            Interval::closed(6, 8),
+            Interval::closed(2, 3),
+            Interval::closed(4, 5),
+            Interval::closed(5, 7),
+            Interval::closed(7, 9),
+            Interval::closed(2, 8),
+            Interval::closed(3, 7),
+            Interval::closed(6, 6),
+            Interval::closed(4, 6),
+        ];
+        let plan = plan(&assignments);
+        let trimmed = plan.trimmed.iter().flatten().copied().collect::<Vec<_>>();
+        assert_eq!(interval::merge(assignments), interval::merge(trimmed.clone()));
+        let total = trimmed.iter().map(Interval::len).sum::<u64>();
+        assert_eq!(Interval::closed(2, 9).len(), total);
+        for (a, t) in assignments.iter().zip(&plan.trimmed) {
+            assert!(t.is_none_or(|t| a.contains_interval(&t)));
+        }
+    }
+}
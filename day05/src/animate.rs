@@ -0,0 +1,255 @@
+use std::io::{self, BufRead, Write};
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::thread;
+use std::time::Duration;
+
+use crate::crane::{Crane, Stacks};
+use crate::render::render_with;
+use crate::simulate::SimulationError;
+use crate::{run, Instruction};
+
+const MIN_DELAY: Duration = Duration::from_millis(10);
+const MAX_DELAY: Duration = Duration::from_secs(5);
+
+const CLEAR: &str = "\x1b[2J\x1b[H";
+const HIGHLIGHT: &str = "\x1b[1;7m";
+const RESET: &str = "\x1b[0m";
+
+/// The stacks after one step of the simulation.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Frame {
+    pub crates: Stacks,
+    /// The instruction that led here, with its number from 1, and `None` for
+    /// the starting arrangement.
+    pub step: Option<(usize, String)>,
+}
+
+/// Runs the simulation up front so the player can jump anywhere.
+pub fn frames(crane: &dyn Crane, mut crates: Stacks, instructions: &[Instruction]) -> Result<Vec<Frame>, SimulationError> {
+    let mut frames = vec![Frame { crates: crates.clone(), step: None }];
+    run(crane, &mut crates, instructions, |i, instruction, crates| {
+        frames.push(Frame { crates: crates.clone(), step: Some((i + 1, instruction.to_string())) });
+    })?;
+    Ok(frames)
+}
+
+/// A command typed while the animation runs, one per line.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Command {
+    /// Pauses, or resumes when paused.
+    Pause,
+    /// Pauses and shows the next step.
+    Step,
+    /// Shows the stacks after step `n`, 0 being the start.
+    Jump(usize),
+    Faster,
+    Slower,
+    Quit,
+}
+
+impl Command {
+    pub fn parse(line: &str) -> Option<Command> {
+        let mut words = line.split_whitespace();
+        let command = match words.next()? {
+            "p" => Command::Pause,
+            "s" => Command::Step,
+            "j" => Command::Jump(words.next()?.parse().ok()?),
+            "+" => Command::Faster,
+            "-" => Command::Slower,
+            "q" => Command::Quit,
+            _ => return None,
+        };
+        words.next().is_none().then_some(command)
+    }
+}
+
+/// Where the animation is and how it moves on.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Player {
+    pub frame: usize,
+    pub frames: usize,
+    pub paused: bool,
+    pub delay: Duration,
+    pub quit: bool,
+}
+
+impl Player {
+    pub fn new(frames: usize, delay: Duration) -> Self {
+        Player { frame: 0, frames, paused: false, delay: delay.clamp(MIN_DELAY, MAX_DELAY), quit: false }
+    }
+
+    fn last(&self) -> usize {
+        self.frames.saturating_sub(1)
+    }
+
+    pub fn apply(&mut self, command: Command) {
+        match command {
+            Command::Pause => self.paused = !self.paused,
+            Command::Step => {
+                self.paused = true;
+                self.frame = (self.frame + 1).min(self.last());
+            }
+            Command::Jump(n) => self.frame = n.min(self.last()),
+            Command::Faster => self.delay = (self.delay / 2).max(MIN_DELAY),
+            Command::Slower => self.delay = (self.delay * 2).min(MAX_DELAY),
+            Command::Quit => self.quit = true,
+        }
+    }
+
+    /// Moves on a frame when it is time to, unless paused.
+    pub fn tick(&mut self) {
+        if !self.paused {
+            self.frame = (self.frame + 1).min(self.last());
+        }
+    }
+
+    /// Playing stops at the last frame; a paused player waits for commands.
+    pub fn finished(&self) -> bool {
+        self.quit || (!self.paused && self.frame == self.last())
+    }
+}
+
+/// Draws `frames[player.frame]` with the crates the last move placed
+/// highlighted: the top `num` crates of its destination. A move from a stack
+/// onto itself moves nothing, so nothing is highlighted.
+pub fn draw(frames: &[Frame], instructions: &[Instruction], player: &Player) -> String {
+    let frame = &frames[player.frame];
+    let step = frame.step.as_ref().map(|(n, _)| &instructions[n - 1]);
+    let moved = step.filter(|i| i.source != i.destination).map(|i| {
+        // Only moves that succeeded have a frame, so the stack exists.
+        let destination = i.destination - 1;
+        (destination, frame.crates[destination].len().saturating_sub(i.num as usize))
+    });
+    let diagram = render_with(&frame.crates, |stack, level, drawn| match moved {
+        Some((destination, from)) if stack == destination && level >= from => format!("{}{}{}", HIGHLIGHT, drawn, RESET),
+        _ => drawn,
+    });
+
+    let status = match &frame.step {
+        Some((n, text)) => format!("step {}/{}: {}", n, frames.len() - 1, text),
+        None => format!("step 0/{}: start", frames.len() - 1),
+    };
+    format!(
+        "{}{}\n{}, delay {:?}\n[p]ause  [s]tep  [j N] jump  [+/-] speed  [q]uit, then enter\n",
+        diagram,
+        status,
+        if player.paused { "paused" } else { "playing" },
+        player.delay
+    )
+}
+
+/// Plays `frames` in the terminal, reading commands from stdin on their own
+/// thread so the timer keeps running while nobody types.
+pub fn animate(frames: &[Frame], instructions: &[Instruction], delay: Duration) -> io::Result<()> {
+    let (tx, rx) = mpsc::channel();
+    thread::spawn(move || {
+        for line in io::stdin().lock().lines() {
+            let Ok(line) = line else { break };
+            if let Some(command) = Command::parse(&line) {
+                if tx.send(command).is_err() {
+                    break;
+                }
+            }
+        }
+    });
+
+    let mut player = Player::new(frames.len(), delay);
+    let mut stdin_open = true;
+    let mut out = io::stdout().lock();
+    loop {
+        write!(out, "{}{}", CLEAR, draw(frames, instructions, &player))?;
+        out.flush()?;
+        if player.finished() {
+            break;
+        }
+
+        if stdin_open {
+            match rx.recv_timeout(player.delay) {
+                Ok(command) => player.apply(command),
+                Err(RecvTimeoutError::Timeout) => player.tick(),
+                Err(RecvTimeoutError::Disconnected) => stdin_open = false,
+            }
+        } else if player.paused {
+            // Nothing can resume the animation any more.
+            break;
+        } else {
+            thread::sleep(player.delay);
+            player.tick();
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::crane::CrateMover9001;
+
+    fn example() -> (Vec<Frame>, Vec<Instruction>) {
+        let crates = vec!["ZN".chars().collect(), "MCD".chars().collect(), "P".chars().collect()];
+        let instructions = vec![
+            Instruction { num: 1, source: 2, destination: 1 },
+            Instruction { num: 3, source: 1, destination: 3 },
+        ];
+        (frames(&CrateMover9001, crates, &instructions).unwrap(), instructions)
+    }
+
+    #[test]
+    fn test_commands() {
+        assert_eq!(Some(Command::Pause), Command::parse("p"));
+        assert_eq!(Some(Command::Jump(12)), Command::parse(" j 12 "));
+        assert_eq!(Some(Command::Faster), Command::parse("+"));
+        assert_eq!(None, Command::parse("j"));
+        assert_eq!(None, Command::parse("j x"));
+        assert_eq!(None, Command::parse("q now"));
+        assert_eq!(None, Command::parse(""));
+    }
+
+    #[test]
+    fn test_player() {
+        let mut player = Player::new(3, Duration::from_millis(100));
+        player.tick();
+        assert_eq!(1, player.frame);
+        player.apply(Command::Pause);
+        player.tick();
+        assert_eq!(1, player.frame);
+        player.apply(Command::Jump(0));
+        player.apply(Command::Step);
+        assert_eq!((1, true), (player.frame, player.paused));
+        player.apply(Command::Jump(99));
+        assert_eq!(2, player.frame);
+        assert!(!player.finished());
+        player.apply(Command::Pause);
+        assert!(player.finished());
+
+        player.apply(Command::Faster);
+        assert_eq!(Duration::from_millis(50), player.delay);
+        for _ in 0..20 {
+            player.apply(Command::Slower);
+        }
+        assert_eq!(MAX_DELAY, player.delay);
+    }
+
+    #[test]
+    fn test_frames_and_highlights() {
+        let (frames, instructions) = example();
+        assert_eq!(3, frames.len());
+        assert_eq!(Some((2, "move 3 from 1 to 3".to_string())), frames[2].step);
+
+        let mut player = Player::new(frames.len(), Duration::from_millis(100));
+        assert!(!draw(&frames, &instructions, &player).contains(HIGHLIGHT));
+        player.frame = 2;
+        let drawn = draw(&frames, &instructions, &player);
+        // P stays put; Z, N and D arrived on top of it.
+        assert!(drawn.contains("[P]"));
+        assert_eq!(3, drawn.matches(HIGHLIGHT).count());
+        assert!(drawn.contains(&format!("{}[D]{}", HIGHLIGHT, RESET)));
+        assert!(drawn.contains("step 2/2: move 3 from 1 to 3"));
+
+        // Moving crates onto their own stack leaves them where they were.
+        let instructions = vec![Instruction { num: 2, source: 3, destination: 3 }];
+        let same = super::frames(&CrateMover9001, frames[2].crates.clone(), &instructions).unwrap();
+        player.frame = 1;
+        assert!(!draw(&same, &instructions, &player).contains(HIGHLIGHT));
+    }
+}
@@ -0,0 +1,279 @@
+use std::collections::VecDeque;
+use std::error::Error;
+use std::fmt;
+
+use crate::Instruction;
+
+/// The stacks, bottom crate first.
+pub type Stacks = Vec<VecDeque<char>>;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CraneError {
+    /// Stacks are numbered from 1 as in the input.
+    NoSuchStack { stack: usize, stacks: usize },
+    NotEnoughCrates { stack: usize, wanted: usize, available: usize },
+    TooHigh { stack: usize, height: usize, max: usize },
+}
+
+impl fmt::Display for CraneError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::NoSuchStack { stack, stacks } => write!(f, "no stack {}, stacks are 1 to {}", stack, stacks),
+            Self::NotEnoughCrates { stack, wanted, available } => {
+                write!(f, "cannot take {} crates from stack {}, it holds {}", wanted, stack, available)
+            }
+            Self::TooHigh { stack, height, max } => {
+                write!(f, "stack {} would be {} crates high, the limit is {}", stack, height, max)
+            }
+        }
+    }
+}
+
+impl Error for CraneError {}
+
+/// A crane model: how it carries out a move.
+///
+/// A crane checks an instruction before moving anything, so a failed move
+/// leaves the stacks as they were.
+pub trait Crane {
+    fn name(&self) -> String;
+
+    fn execute(&self, crates: &mut Stacks, instruction: &Instruction) -> Result<(), CraneError>;
+}
+
+impl<C: Crane + ?Sized> Crane for Box<C> {
+    fn name(&self) -> String {
+        self.as_ref().name()
+    }
+
+    fn execute(&self, crates: &mut Stacks, instruction: &Instruction) -> Result<(), CraneError> {
+        self.as_ref().execute(crates, instruction)
+    }
+}
+
+/// Position in `crates` of the stack numbered `stack` from 1.
+fn index(crates: &Stacks, stack: usize) -> Result<usize, CraneError> {
+    if stack == 0 || stack > crates.len() {
+        return Err(CraneError::NoSuchStack { stack, stacks: crates.len() });
+    }
+    Ok(stack - 1)
+}
+
+/// Checks that both stacks exist and the source holds enough crates, and
+/// returns the positions of the source and destination stacks.
+fn check(crates: &Stacks, instruction: &Instruction) -> Result<(usize, usize), CraneError> {
+    let source = index(crates, instruction.source)?;
+    let destination = index(crates, instruction.destination)?;
+    let wanted = instruction.num as usize;
+    if wanted > crates[source].len() {
+        return Err(CraneError::NotEnoughCrates {
+            stack: instruction.source,
+            wanted,
+            available: crates[source].len(),
+        });
+    }
+    Ok((source, destination))
+}
+
+/// Lifts the crates to move off the source stack, top crate last, and
+/// returns them with the position of the destination stack.
+///
+/// Moving crates from a stack onto itself puts each lift straight back, so
+/// after checking the instruction nothing is lifted at all.
+fn lift(crates: &mut Stacks, instruction: &Instruction) -> Result<(VecDeque<char>, usize), CraneError> {
+    let (source, destination) = check(crates, instruction)?;
+    if source == destination {
+        return Ok((VecDeque::new(), destination));
+    }
+    let source = &mut crates[source];
+    Ok((source.split_off(source.len() - instruction.num as usize), destination))
+}
+
+/// Moves one crate at a time, so the moved crates end up reversed.
+#[derive(Debug, Clone, Copy)]
+pub struct CrateMover9000;
+
+impl Crane for CrateMover9000 {
+    fn name(&self) -> String {
+        "CrateMover 9000".to_string()
+    }
+
+    fn execute(&self, crates: &mut Stacks, instruction: &Instruction) -> Result<(), CraneError> {
+        let (lifted, destination) = lift(crates, instruction)?;
+        crates[destination].extend(lifted.into_iter().rev());
+        Ok(())
+    }
+}
+
+/// Moves all the crates at once, keeping their order.
+#[derive(Debug, Clone, Copy)]
+pub struct CrateMover9001;
+
+impl Crane for CrateMover9001 {
+    fn name(&self) -> String {
+        "CrateMover 9001".to_string()
+    }
+
+    fn execute(&self, crates: &mut Stacks, instruction: &Instruction) -> Result<(), CraneError> {
+        let (mut lifted, destination) = lift(crates, instruction)?;
+        crates[destination].append(&mut lifted);
+        Ok(())
+    }
+}
+
+/// Lifts at most `capacity` crates at a time, keeping the order within
+/// each lift. A capacity of 1 is a CrateMover 9000.
+#[derive(Debug, Clone, Copy)]
+pub struct Limited {
+    pub capacity: usize,
+}
+
+impl Crane for Limited {
+    fn name(&self) -> String {
+        format!("limited to {} crates per lift", self.capacity)
+    }
+
+    fn execute(&self, crates: &mut Stacks, instruction: &Instruction) -> Result<(), CraneError> {
+        let (lifted, destination) = lift(crates, instruction)?;
+        let lifted = lifted.into_iter().collect::<Vec<_>>();
+        // The top crates are lifted first.
+        for chunk in lifted.rchunks(self.capacity.max(1)) {
+            crates[destination].extend(chunk);
+        }
+        Ok(())
+    }
+}
+
+/// Lifts all the crates at once like a CrateMover 9001, but each of its
+/// clamps holds `size` crates and turns them upside down, counting from the
+/// top. A size of 1 is a CrateMover 9001; a size of at least the number of
+/// crates moved is a CrateMover 9000.
+#[derive(Debug, Clone, Copy)]
+pub struct ReversedChunks {
+    pub size: usize,
+}
+
+impl Crane for ReversedChunks {
+    fn name(&self) -> String {
+        format!("reversing chunks of {} crates", self.size)
+    }
+
+    fn execute(&self, crates: &mut Stacks, instruction: &Instruction) -> Result<(), CraneError> {
+        let (lifted, destination) = lift(crates, instruction)?;
+        let lifted = lifted.into_iter().collect::<Vec<_>>();
+        let destination = &mut crates[destination];
+        for chunk in lifted.rchunks(self.size.max(1)).rev() {
+            destination.extend(chunk.iter().rev());
+        }
+        Ok(())
+    }
+}
+
+/// Any crane, refusing moves that would stack a destination higher than
+/// `max` crates.
+#[derive(Debug, Clone, Copy)]
+pub struct MaxHeight<C> {
+    pub crane: C,
+    pub max: usize,
+}
+
+impl<C: Crane> Crane for MaxHeight<C> {
+    fn name(&self) -> String {
+        format!("{}, at most {} crates high", self.crane.name(), self.max)
+    }
+
+    fn execute(&self, crates: &mut Stacks, instruction: &Instruction) -> Result<(), CraneError> {
+        // A move that cannot happen at all is reported as such first.
+        let (source, destination) = check(crates, instruction)?;
+        // Moving a stack onto itself does not change its height.
+        let added = if source == destination { 0 } else { instruction.num as usize };
+        let height = crates[destination].len() + added;
+        if height > self.max {
+            return Err(CraneError::TooHigh { stack: instruction.destination, height, max: self.max });
+        }
+        self.crane.execute(crates, instruction)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stacks() -> Stacks {
+        vec!["ABCD".chars().collect(), VecDeque::new()]
+    }
+
+    fn move_all(crane: &dyn Crane) -> String {
+        let mut crates = stacks();
+        crane.execute(&mut crates, &Instruction { num: 4, source: 1, destination: 2 }).unwrap();
+        crates[1].iter().collect()
+    }
+
+    #[test]
+    fn test_models() {
+        assert_eq!("DCBA", move_all(&CrateMover9000));
+        assert_eq!("ABCD", move_all(&CrateMover9001));
+        // C and D go first, then A and B on top of them.
+        assert_eq!("CDAB", move_all(&Limited { capacity: 2 }));
+        assert_eq!("DCBA", move_all(&Limited { capacity: 1 }));
+        // The top clamp holds D and C, the bottom one B and A.
+        assert_eq!("BADC", move_all(&ReversedChunks { size: 2 }));
+        assert_eq!("ABCD", move_all(&ReversedChunks { size: 1 }));
+        assert_eq!("DCBA", move_all(&ReversedChunks { size: 9 }));
+        assert_eq!("ABCD", move_all(&MaxHeight { crane: CrateMover9001, max: 4 }));
+    }
+
+    #[test]
+    fn test_same_stack_is_unchanged() {
+        let cranes: [&dyn Crane; 5] = [
+            &CrateMover9000,
+            &CrateMover9001,
+            &Limited { capacity: 3 },
+            &ReversedChunks { size: 2 },
+            &MaxHeight { crane: CrateMover9000, max: 4 },
+        ];
+        for crane in cranes {
+            let mut crates = stacks();
+            crane.execute(&mut crates, &Instruction { num: 3, source: 1, destination: 1 }).unwrap();
+            assert_eq!(stacks(), crates, "{}", crane.name());
+        }
+        // Still checked before doing nothing.
+        assert_eq!(
+            Err(CraneError::NotEnoughCrates { stack: 2, wanted: 1, available: 0 }),
+            CrateMover9000.execute(&mut stacks(), &Instruction { num: 1, source: 2, destination: 2 })
+        );
+    }
+
+    #[test]
+    fn test_errors_leave_stacks_untouched() {
+        let mut crates = stacks();
+        assert_eq!(
+            Err(CraneError::NotEnoughCrates { stack: 1, wanted: 5, available: 4 }),
+            CrateMover9000.execute(&mut crates, &Instruction { num: 5, source: 1, destination: 2 })
+        );
+        assert_eq!(
+            Err(CraneError::NoSuchStack { stack: 3, stacks: 2 }),
+            CrateMover9001.execute(&mut crates, &Instruction { num: 1, source: 1, destination: 3 })
+        );
+        let crane = MaxHeight { crane: CrateMover9001, max: 3 };
+        assert_eq!(
+            Err(CraneError::TooHigh { stack: 2, height: 4, max: 3 }),
+            crane.execute(&mut crates, &Instruction { num: 4, source: 1, destination: 2 })
+        );
+        assert_eq!(
+            Err(CraneError::NoSuchStack { stack: 0, stacks: 2 }),
+            CrateMover9000.execute(&mut crates, &Instruction { num: 1, source: 0, destination: 2 })
+        );
+        // Too few crates and too high: the move could never happen.
+        assert_eq!(
+            Err(CraneError::NotEnoughCrates { stack: 1, wanted: 50, available: 4 }),
+            crane.execute(&mut crates, &Instruction { num: 50, source: 1, destination: 2 })
+        );
+        assert_eq!(
+            Err(CraneError::NoSuchStack { stack: 3, stacks: 2 }),
+            crane.execute(&mut crates, &Instruction { num: 4, source: 3, destination: 2 })
+        );
+        assert_eq!(stacks(), crates);
+        assert_eq!("CrateMover 9001, at most 3 crates high", crane.name());
+    }
+}
@@ -1,28 +1,117 @@
 use std::collections::VecDeque;
 use std::convert::TryFrom;
+use std::error::Error;
+use std::fmt;
 use std::num::ParseIntError;
-use std::{env, fs, io, path::Path};
+use std::time::Duration;
+use std::{env, fs, io, path::Path, process};
 
 use nom::branch::alt;
 use nom::bytes::complete::tag;
 use nom::character::complete;
 use nom::character::complete::{anychar, digit1, line_ending, not_line_ending, space1};
 use nom::combinator::{eof, opt};
-use nom::multi::{count, many1, separated_list1};
+use nom::multi::{many1, separated_list1};
 use nom::sequence::{delimited, preceded, terminated, tuple};
-use nom::IResult;
+use nom::{IResult, Offset};
 
-fn main() -> io::Result<()> {
+mod animate;
+mod crane;
+mod render;
+mod simulate;
+
+use crane::{Crane, CrateMover9000, CrateMover9001, Limited, MaxHeight, ReversedChunks, Stacks};
+use simulate::SimulationError;
+
+fn main() {
+    // Errors are shown with `Display`, so a failed instruction comes with the
+    // drawing of the stacks it failed on.
+    if let Err(e) = cli() {
+        eprintln!("error: {}", e.to_string().trim_end());
+        process::exit(1);
+    }
+}
+
+fn cli() -> io::Result<()> {
     let mut dir = env::current_exe()?;
     dir.pop();
     let dir = dir.join(Path::new("../../day05/input.txt"));
 
+    let (flags, args): (Vec<String>, Vec<String>) = env::args().skip(1).partition(|a| a.starts_with("--"));
     let input = fs::read_to_string(dir)?;
-    println!("part1: {}", part1(&input));
-    println!("part2: {}", part2(&input));
+    let model = flags.iter().find_map(|f| f.strip_prefix("--crane="));
+    let max_height = flags.iter().find_map(|f| f.strip_prefix("--max-height="));
+    let trace = flags.iter().any(|f| f == "--trace");
+    let command = args.first().map(String::as_str);
+    let animate = command == Some("animate");
+    let validate = command == Some("validate");
+    let (mut crates, instructions) = read_puzzle(&input).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+    if model.is_none() && max_height.is_none() && !trace && !animate && !validate {
+        let invalid = |e| io::Error::new(io::ErrorKind::InvalidData, e);
+        println!("part1: {}", part1(crates.clone(), &instructions).map_err(invalid)?);
+        println!("part2: {}", part2(crates, &instructions).map_err(invalid)?);
+        return Ok(());
+    }
+
+    let mut crane = crane_model(model.unwrap_or("9000")).ok_or_else(|| {
+        let usage = format!(
+            "unknown crane {:?}, expected 9000, 9001, limited:<capacity> or reversed:<size>",
+            model.unwrap_or_default()
+        );
+        io::Error::new(io::ErrorKind::InvalidInput, usage)
+    })?;
+    if let Some(max) = max_height {
+        let max = max.parse().map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+        crane = Box::new(MaxHeight { crane, max });
+    }
+    if validate {
+        let errors = simulate::validate(crane.as_ref(), crates, &instructions);
+        for error in &errors {
+            println!("{}", error);
+        }
+        if errors.is_empty() {
+            println!("{}: all {} instructions valid", crane.name(), instructions.len());
+        } else {
+            println!("{}: {} of {} instructions invalid", crane.name(), errors.len(), instructions.len());
+        }
+        return Ok(());
+    }
+    if animate {
+        let delay = match flags.iter().find_map(|f| f.strip_prefix("--delay=")) {
+            Some(ms) => ms.parse().map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?,
+            None => 200,
+        };
+        let frames = animate::frames(crane.as_ref(), crates, &instructions)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        return animate::animate(&frames, &instructions, Duration::from_millis(delay));
+    }
+    if trace {
+        print!("{}", render::render(&crates));
+    }
+    run(crane.as_ref(), &mut crates, &instructions, |i, instruction, crates| {
+        if trace {
+            println!("\n{}: {}", i + 1, instruction);
+            print!("{}", render::render(crates));
+        }
+    })
+    .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+    println!("{}: {}", crane.name(), tops(crates));
     Ok(())
 }
 
+/// The crane named on the command line: `9000`, `9001`, `limited:<capacity>`
+/// or `reversed:<size>`.
+fn crane_model(name: &str) -> Option<Box<dyn Crane>> {
+    let sized = |prefix: &str| name.strip_prefix(prefix).and_then(|n| n.parse::<usize>().ok()).filter(|&n| n > 0);
+    match name {
+        "9000" => Some(Box::new(CrateMover9000)),
+        "9001" => Some(Box::new(CrateMover9001)),
+        _ => sized("limited:")
+            .map(|capacity| Box::new(Limited { capacity }) as Box<dyn Crane>)
+            .or_else(|| sized("reversed:").map(|size| Box::new(ReversedChunks { size }) as Box<dyn Crane>)),
+    }
+}
+
 #[derive(Debug)]
 struct Instruction {
     num: u32,
@@ -36,18 +125,62 @@ impl TryFrom<(&str, &str, &str)> for Instruction {
     fn try_from(input: (&str, &str, &str)) -> Result<Self, Self::Error> {
         Ok(Instruction {
             num: input.0.parse()?,
-            source: input.1.parse::<usize>()? - 1,
-            destination: input.2.parse::<usize>()? - 1,
+            source: input.1.parse()?,
+            destination: input.2.parse()?,
         })
     }
 }
 
+impl fmt::Display for Instruction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "move {} from {} to {}", self.num, self.source, self.destination)
+    }
+}
+
+/// Input the parser stopped at. Lines are numbered from 1.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct ParseError {
+    line: usize,
+    text: String,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "line {}: cannot parse {:?}", self.line, self.text)
+    }
+}
+
+impl Error for ParseError {}
+
+/// The starting stacks and the instructions, or the line that does not parse.
+fn read_puzzle(input: &str) -> Result<(Stacks, Vec<Instruction>), ParseError> {
+    let rest = match parse_input(input) {
+        Ok((_, puzzle)) => return Ok(puzzle),
+        Err(nom::Err::Error(e) | nom::Err::Failure(e)) => e.input,
+        Err(nom::Err::Incomplete(_)) => "",
+    };
+    let offset = input.offset(rest);
+    let start = input[..offset].rfind('\n').map_or(0, |i| i + 1);
+    Err(ParseError {
+        line: input[..start].matches('\n').count() + 1,
+        text: input[start..].lines().next().unwrap_or("").to_string(),
+    })
+}
+
 fn parse_input(input: &str) -> IResult<&str, (Vec<VecDeque<char>>, Vec<Instruction>)> {
-    let (input, rows) = many1(parse_crates)(input)?;
-    let (input, _) = terminated(not_line_ending, count(line_ending, 2))(input)?;
+    let (input, crates) = parse_diagram(input)?;
+    let (input, _) = line_ending(input)?;
     let (input, instructions) = many1(parse_instruction)(input)?;
     let (input, _) = eof(input)?;
 
+    Ok((input, (crates, instructions)))
+}
+
+/// The drawing of the stacks, up to and including the row of stack numbers.
+fn parse_diagram(input: &str) -> IResult<&str, Vec<VecDeque<char>>> {
+    let (input, rows) = many1(parse_crates)(input)?;
+    let (input, _) = terminated(not_line_ending, line_ending)(input)?;
+
     let mut crates: Vec<VecDeque<char>> = Vec::with_capacity(rows[0].len());
     for _ in 0..rows[0].len() {
         crates.push(VecDeque::new());
@@ -61,7 +194,7 @@ fn parse_input(input: &str) -> IResult<&str, (Vec<VecDeque<char>>, Vec<Instructi
         }
     }
 
-    Ok((input, (crates, instructions)))
+    Ok((input, crates))
 }
 
 fn parse_crates(input: &str) -> IResult<&str, Vec<char>> {
@@ -86,41 +219,49 @@ fn parse_instruction(input: &str) -> IResult<&str, Instruction> {
     let (input, source) = preceded(tuple((space1, tag("from"), space1)), digit1)(input)?;
     let (input, destination) = preceded(tuple((space1, tag("to"), space1)), digit1)(input)?;
     let (input, _) = opt(line_ending)(input)?;
-    Ok((
-        input,
-        Instruction::try_from((num, source, destination)).unwrap(),
-    ))
+    // Numbers too large to hold fail to parse rather than panic.
+    let instruction = Instruction::try_from((num, source, destination))
+        .map_err(|_| nom::Err::Failure(nom::error::Error::new(num, nom::error::ErrorKind::Digit)))?;
+    Ok((input, instruction))
 }
 
-fn part1(input: &str) -> String {
-    let (_, (mut crates, instructions)) = parse_input(input).unwrap();
-
-    for instruction in instructions {
-        for _ in 0..(instruction.num) {
-            let c = crates[instruction.source].pop_back().unwrap();
-            crates[instruction.destination].push_back(c);
-        }
+/// Runs `instructions` in order with `crane`, calling `step` with each
+/// instruction's index and the stacks after it. Stops at the first
+/// instruction the crane cannot carry out.
+fn run(
+    crane: &dyn Crane,
+    crates: &mut Stacks,
+    instructions: &[Instruction],
+    mut step: impl FnMut(usize, &Instruction, &Stacks),
+) -> Result<(), SimulationError> {
+    for (i, instruction) in instructions.iter().enumerate() {
+        crane
+            .execute(crates, instruction)
+            .map_err(|e| SimulationError::new(i, instruction, e, crates))?;
+        step(i, instruction, crates);
     }
+    Ok(())
+}
 
+fn tops(crates: Stacks) -> String {
     crates
         .into_iter()
         .map(|mut c| c.pop_back().unwrap_or(' '))
         .collect::<String>()
 }
 
-fn part2(input: &str) -> String {
-    let (_, (mut crates, instructions)) = parse_input(input).unwrap();
+/// Runs every instruction with `crane` and reads off the top crates.
+fn rearrange(crane: &dyn Crane, mut crates: Stacks, instructions: &[Instruction]) -> Result<String, SimulationError> {
+    run(crane, &mut crates, instructions, |_, _, _| {})?;
+    Ok(tops(crates))
+}
 
-    for instruction in instructions {
-        let split = crates[instruction.source].len() - (instruction.num as usize);
-        let mut c = crates[instruction.source].split_off(split);
-        crates[instruction.destination].append(&mut c);
-    }
+fn part1(crates: Stacks, instructions: &[Instruction]) -> Result<String, SimulationError> {
+    rearrange(&CrateMover9000, crates, instructions)
+}
 
-    crates
-        .into_iter()
-        .map(|mut c| c.pop_back().unwrap_or(' '))
-        .collect::<String>()
+fn part2(crates: Stacks, instructions: &[Instruction]) -> Result<String, SimulationError> {
+    rearrange(&CrateMover9001, crates, instructions)
 }
 
 #[cfg(test)]
@@ -139,11 +280,61 @@ move 1 from 1 to 2";
 
     #[test]
     fn test_part1() {
-        assert_eq!("CMZ", part1(INPUT));
+        let (crates, instructions) = read_puzzle(INPUT).unwrap();
+        assert_eq!(Ok("CMZ".to_string()), part1(crates, &instructions));
     }
 
     #[test]
     fn test_part2() {
-        assert_eq!("MCD", part2(INPUT));
+        let (crates, instructions) = read_puzzle(INPUT).unwrap();
+        assert_eq!(Ok("MCD".to_string()), part2(crates, &instructions));
+    }
+
+    #[test]
+    fn test_trace() {
+        let (mut crates, instructions) = read_puzzle(INPUT).unwrap();
+        let mut steps = Vec::new();
+        run(&CrateMover9000, &mut crates, &instructions, |i, instruction, crates| {
+            steps.push(format!("{}: {}\n{}", i + 1, instruction, render::render(crates)));
+        })
+        .unwrap();
+        assert_eq!(4, steps.len());
+        assert_eq!(
+            "1: move 1 from 2 to 1
+[D]        
+[N] [C]    
+[Z] [M] [P]
+ 1   2   3 
+",
+            steps[0]
+        );
+    }
+
+    #[test]
+    fn test_crane_models() {
+        let (crates, instructions) = read_puzzle(INPUT).unwrap();
+        let rearrange = |crane: &dyn Crane| rearrange(crane, crates.clone(), &instructions);
+        assert_eq!(Ok("CMZ".to_string()), rearrange(crane_model("limited:1").unwrap().as_ref()));
+        assert_eq!(Ok("MCD".to_string()), rearrange(crane_model("reversed:1").unwrap().as_ref()));
+        assert!(crane_model("limited:0").is_none());
+        assert!(crane_model("9002").is_none());
+
+        // Stack 3 reaches four crates on the second move.
+        let crane = MaxHeight { crane: CrateMover9000, max: 3 };
+        let error = rearrange(&crane).unwrap_err();
+        assert_eq!((2, crane::CraneError::TooHigh { stack: 3, height: 4, max: 3 }), (error.number, error.error));
+        assert_eq!("move 3 from 1 to 3", error.instruction);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let input = INPUT.replace("move 3 from 1 to 3", "move 99999999999 from 1 to 3");
+        assert_eq!(
+            ParseError { line: 7, text: "move 99999999999 from 1 to 3".into() },
+            read_puzzle(&input).unwrap_err()
+        );
+        let input = INPUT.replace("move 2 from 2 to 1", "move two from 2 to 1");
+        let error = read_puzzle(&input).unwrap_err();
+        assert_eq!("line 8: cannot parse \"move two from 2 to 1\"", error.to_string());
     }
 }
@@ -0,0 +1,62 @@
+use std::fmt::Write;
+
+use crate::crane::Stacks;
+
+/// Draws the stacks the way the puzzle input does: one row per level from
+/// the top, every stack three characters wide with a space between, then
+/// the row of stack numbers. Every line is padded to the full width and
+/// ends in a newline.
+pub fn render(crates: &Stacks) -> String {
+    render_with(crates, |_, _, drawn| drawn)
+}
+
+/// Like [`render`], passing every crate through `paint` with its stack and
+/// level, both from 0, to decorate it.
+pub fn render_with(crates: &Stacks, paint: impl Fn(usize, usize, String) -> String) -> String {
+    let height = crates.iter().map(|c| c.len()).max().unwrap_or(0);
+    let mut out = String::new();
+    for level in (0..height).rev() {
+        let row = crates
+            .iter()
+            .enumerate()
+            .map(|(i, stack)| stack.get(level).map_or("   ".to_string(), |c| paint(i, level, format!("[{}]", c))))
+            .collect::<Vec<_>>();
+        writeln!(out, "{}", row.join(" ")).unwrap();
+    }
+    let numbers = (1..=crates.len()).map(|n| format!("{:^3}", n)).collect::<Vec<_>>();
+    writeln!(out, "{}", numbers.join(" ")).unwrap();
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse_diagram;
+
+    const DIAGRAM: &str = "    [D]    
+[N] [C]    
+[Z] [M] [P]
+ 1   2   3 
+";
+
+    #[test]
+    fn test_round_trip() {
+        let (rest, crates) = parse_diagram(DIAGRAM).unwrap();
+        assert_eq!("", rest);
+        assert_eq!(DIAGRAM, render(&crates));
+
+        let tall = "[A]            
+[B]         [E]
+[C] [D]     [F]
+ 1   2   3   4 
+";
+        assert_eq!(tall, render(&parse_diagram(tall).unwrap().1));
+    }
+
+    #[test]
+    fn test_render_after_moves() {
+        let crates = vec!["ZN".chars().collect(), "MCD".chars().collect(), "".chars().collect()];
+        assert_eq!(DIAGRAM.replace("[P]", "   "), render(&crates));
+        assert_eq!(" 1   2 \n", render(&vec![Default::default(), Default::default()]));
+    }
+}
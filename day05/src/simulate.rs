@@ -0,0 +1,89 @@
+use std::error::Error;
+use std::fmt;
+
+use crate::crane::{Crane, CraneError, Stacks};
+use crate::render::render;
+use crate::Instruction;
+
+/// An instruction the crane could not carry out.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SimulationError {
+    /// Instructions are numbered from 1.
+    pub number: usize,
+    /// The instruction in the input's notation. It is written out again from
+    /// the parsed numbers, so spacing may differ from the input line.
+    pub instruction: String,
+    pub error: CraneError,
+    /// The stacks just before the instruction, which it left untouched.
+    pub crates: Stacks,
+}
+
+impl SimulationError {
+    pub fn new(index: usize, instruction: &Instruction, error: CraneError, crates: &Stacks) -> Self {
+        SimulationError { number: index + 1, instruction: instruction.to_string(), error, crates: crates.clone() }
+    }
+}
+
+impl fmt::Display for SimulationError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "instruction {} ({}): {}", self.number, self.instruction, self.error)?;
+        write!(f, "{}", render(&self.crates))
+    }
+}
+
+impl Error for SimulationError {}
+
+/// Every instruction `crane` cannot carry out, in order. A failed
+/// instruction is skipped and the rest run on the stacks as they were.
+pub fn validate(crane: &dyn Crane, mut crates: Stacks, instructions: &[Instruction]) -> Vec<SimulationError> {
+    let mut errors = Vec::new();
+    for (i, instruction) in instructions.iter().enumerate() {
+        if let Err(e) = crane.execute(&mut crates, instruction) {
+            errors.push(SimulationError::new(i, instruction, e, &crates));
+        }
+    }
+    errors
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::crane::{CrateMover9000, CrateMover9001};
+    use crate::read_puzzle;
+
+    const INPUT: &str = "[A]    
+[B] [C]
+ 1   2 
+
+move 3 from 1 to 2
+move 1 from 0 to 2
+move 1 from 2 to 3
+move 2 from 1 to 2
+move 3 from 2 to 1";
+
+    #[test]
+    fn test_validate() {
+        let (crates, instructions) = read_puzzle(INPUT).unwrap();
+        let errors = validate(&CrateMover9000, crates.clone(), &instructions);
+        assert_eq!(
+            vec![
+                (1, CraneError::NotEnoughCrates { stack: 1, wanted: 3, available: 2 }),
+                (2, CraneError::NoSuchStack { stack: 0, stacks: 2 }),
+                (3, CraneError::NoSuchStack { stack: 3, stacks: 2 }),
+            ],
+            errors.iter().map(|e| (e.number, e.error.clone())).collect::<Vec<_>>()
+        );
+        assert!(errors.iter().all(|e| e.crates == crates));
+        assert_eq!(
+            "instruction 1 (move 3 from 1 to 2): cannot take 3 crates from stack 1, it holds 2
+[A]    
+[B] [C]
+ 1   2 
+",
+            errors[0].to_string()
+        );
+
+        // The last move only has the crates for it once the fourth has run.
+        assert_eq!(3, validate(&CrateMover9001, crates, &instructions).len());
+    }
+}